@@ -0,0 +1,46 @@
+//! The cancellation of the running statements with CTRL-C.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether a query is running.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+/// Whether the running query has been cancelled.
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the CTRL-C handler cancelling the running statement instead
+/// of terminating the process. Outside of the statements, such as while a
+/// file is loaded, the process is terminated as usual. The line editor
+/// reads CTRL-C as a key, so the handler isn't called while the input is
+/// typed.
+pub fn install_handler() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        if RUNNING.load(Ordering::Relaxed) {
+            CANCELLED.store(true, Ordering::Relaxed);
+        } else {
+            // The exit status of a process terminated by SIGINT.
+            std::process::exit(130);
+        }
+    })
+}
+
+/// Marks the query as running until the returned guard is dropped.
+pub fn start() -> RunningQuery {
+    CANCELLED.store(false, Ordering::Relaxed);
+    RUNNING.store(true, Ordering::Relaxed);
+    RunningQuery
+}
+
+/// Returns [`true`] if the running query has been cancelled.
+pub fn is_cancelled() -> bool {
+    CANCELLED.load(Ordering::Relaxed)
+}
+
+/// A guard marking a query as running.
+pub struct RunningQuery;
+
+impl Drop for RunningQuery {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Relaxed);
+        CANCELLED.store(false, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,93 @@
+//! The command-line interface.
+
+use clap::Parser;
+
+use prisma_test::{csv_options::CsvOptions, output::OutputMode, spreadsheet::Sheet};
+
+/// Queries the CSV files with a simple PROJECT/FILTER language.
+///
+/// Without a query to execute, the queries are read from the standard
+/// input: interactively when it is a terminal, or to its end otherwise,
+/// every statement ending with `;` and possibly spanning multiple lines.
+/// A query to execute is required when the file is read from the
+/// standard input.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Args {
+    /// The CSV file to query, `-` for the standard input.
+    #[arg(short, long, default_value = "data.csv")]
+    pub file: String,
+    /// Executes the query and exits.
+    #[arg(short, long, value_name = "QUERY")]
+    pub execute: Option<String>,
+    /// The format to output the query results in.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputMode,
+    /// The character separating the fields, `\t` for a tab. `,` when
+    /// another option of the dialect is given.
+    #[arg(short, long, value_name = "CHAR", value_parser = parse_char)]
+    pub delimiter: Option<u8>,
+    /// The character quoting the fields. `"` when another option of the
+    /// dialect is given.
+    #[arg(long, value_name = "CHAR", value_parser = parse_char)]
+    pub quote: Option<u8>,
+    /// The character escaping the quotes within the quoted fields, which
+    /// are escaped by doubling them otherwise.
+    #[arg(long, value_name = "CHAR", value_parser = parse_char)]
+    pub escape: Option<u8>,
+    /// The character starting the lines to skip.
+    #[arg(long, value_name = "CHAR", value_parser = parse_char)]
+    pub comment: Option<u8>,
+    /// The first line holds data rather than the column names, which
+    /// become c1, c2 and so on.
+    #[arg(long)]
+    pub no_header: bool,
+    /// Removes the whitespace around the fields.
+    #[arg(long)]
+    pub trim: bool,
+    /// Reads the file to query as fixed-width, with the layout in the
+    /// given file: a column per line as `NAME START WIDTH TYPE`, the start
+    /// counted from 1 and the type being `integer` or `string`.
+    #[arg(long, value_name = "FILE")]
+    pub layout: Option<String>,
+    /// The table to query when the file is a SQLite database, its only
+    /// table by default.
+    #[arg(long, value_name = "TABLE")]
+    pub table: Option<String>,
+    /// The sheet of the spreadsheets to load: its name, or its position
+    /// counted from 1.
+    #[arg(long, default_value_t)]
+    pub sheet: Sheet,
+}
+
+impl Args {
+    /// Returns the dialect of the CSV files, [`None`] if no option of the
+    /// dialect is given and it is to be guessed for every file.
+    pub fn csv_options(&self) -> Option<CsvOptions> {
+        let guessed = self.delimiter.is_none()
+            && self.quote.is_none()
+            && self.escape.is_none()
+            && self.comment.is_none()
+            && !self.no_header
+            && !self.trim;
+
+        (!guessed).then(|| {
+            CsvOptions::new()
+                .delimiter(self.delimiter.unwrap_or(b','))
+                .quote(self.quote.unwrap_or(b'"'))
+                .escape(self.escape)
+                .comment(self.comment)
+                .has_headers(!self.no_header)
+                .trim(self.trim)
+        })
+    }
+}
+
+/// Parses a single ASCII character, `\t` standing for a tab.
+fn parse_char(s: &str) -> Result<u8, String> {
+    match s.as_bytes() {
+        b"\\t" => Ok(b'\t'),
+        [c] if c.is_ascii() => Ok(*c),
+        _ => Err(format!("expected a single ASCII character: {s}")),
+    }
+}
@@ -0,0 +1,156 @@
+//! The in-memory storage of the column values.
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::table::{ColumnType, ColumnValue};
+
+/// A string column is dictionary-encoded when the number of its distinct
+/// values is at most this ratio of its number of rows, so that most values
+/// repeat often enough to make up for the dictionary.
+const DICTIONARY_MAX_CARDINALITY_RATIO: f64 = 0.1;
+
+/// The values of a single column.
+#[derive(Debug, Clone)]
+pub enum Column {
+    /// Every value is stored as is.
+    Plain(Vec<ColumnValue>),
+    /// Every distinct value is stored once and referenced by its code.
+    Dictionary(DictionaryColumn),
+}
+
+impl Column {
+    /// Creates a column from the values, choosing the encoding.
+    ///
+    /// The string columns with a low cardinality are dictionary-encoded,
+    /// the rest are stored as is.
+    pub fn from_values(values: Vec<ColumnValue>) -> Self {
+        if values.is_empty()
+            || !values
+                .iter()
+                .all(|value| value.get_type() == ColumnType::String)
+        {
+            return Self::Plain(values);
+        }
+
+        let max_distinct = (values.len() as f64 * DICTIONARY_MAX_CARDINALITY_RATIO) as usize;
+
+        match DictionaryColumn::from_values(&values, max_distinct) {
+            Some(dictionary) => Self::Dictionary(dictionary),
+            None => Self::Plain(values),
+        }
+    }
+
+    /// Returns the number of values in the column.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Plain(values) => values.len(),
+            Self::Dictionary(column) => column.codes.len(),
+        }
+    }
+
+    /// Returns the value in the given row.
+    pub fn get(&self, row_idx: usize) -> Option<&ColumnValue> {
+        match self {
+            Self::Plain(values) => values.get(row_idx),
+            Self::Dictionary(column) => column.get(row_idx),
+        }
+    }
+
+    /// Returns the type of the values, if all of them are of the same
+    /// type.
+    pub fn get_type(&self) -> Option<ColumnType> {
+        let mut types = match self {
+            Self::Plain(values) => Box::new(values.iter()) as Box<dyn Iterator<Item = _>>,
+            Self::Dictionary(column) => Box::new(column.dictionary.iter().map(Arc::as_ref)),
+        }
+        .map(ColumnValue::get_type);
+
+        let first = types.next()?;
+        types
+            .all(|column_type| column_type == first)
+            .then_some(first)
+    }
+
+    /// Returns an iterator over the values of the column.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = &ColumnValue> + '_> {
+        match self {
+            Self::Plain(values) => Box::new(values.iter()),
+            Self::Dictionary(column) => Box::new(
+                column
+                    .codes
+                    .iter()
+                    .map(|code| column.dictionary[*code as usize].as_ref()),
+            ),
+        }
+    }
+}
+
+/// A dictionary-encoded column.
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryColumn {
+    /// The distinct values of the column.
+    dictionary: Vec<Arc<ColumnValue>>,
+    /// The code of every distinct value, to look up the filter values.
+    /// The values are shared with the dictionary.
+    codes_by_value: HashMap<Arc<ColumnValue>, u32>,
+    /// The code of the value in each row, which is the position of the
+    /// value in the dictionary.
+    codes: Vec<u32>,
+}
+
+impl DictionaryColumn {
+    /// Encodes the values, or returns [`None`] as soon as there are more
+    /// than `max_distinct` distinct ones.
+    pub fn from_values(values: &[ColumnValue], max_distinct: usize) -> Option<Self> {
+        let mut codes_by_value: HashMap<Arc<ColumnValue>, u32> = HashMap::new();
+        let mut dictionary = Vec::new();
+        let mut codes = Vec::with_capacity(values.len());
+
+        for value in values {
+            let code = match codes_by_value.get(value) {
+                Some(code) => *code,
+                None if dictionary.len() == max_distinct => return None,
+                None => {
+                    let value = Arc::new(value.clone());
+                    let code = dictionary.len() as u32;
+                    dictionary.push(Arc::clone(&value));
+                    codes_by_value.insert(value, code);
+                    code
+                }
+            };
+            codes.push(code);
+        }
+
+        Some(Self {
+            dictionary,
+            codes_by_value,
+            codes,
+        })
+    }
+
+    /// Returns the value in the given row.
+    pub fn get(&self, row_idx: usize) -> Option<&ColumnValue> {
+        self.codes
+            .get(row_idx)
+            .map(|code| self.dictionary[*code as usize].as_ref())
+    }
+
+    /// Returns the code of the value, if it is in the dictionary.
+    pub fn code_of(&self, value: &ColumnValue) -> Option<u32> {
+        self.codes_by_value.get(value).copied()
+    }
+
+    /// Returns the rows holding the value, comparing the codes only.
+    pub fn rows_equal_to(&self, value: &ColumnValue) -> Vec<usize> {
+        let Some(code) = self.code_of(value) else {
+            return Vec::new();
+        };
+
+        self.codes
+            .iter()
+            .enumerate()
+            .filter(|(_, row_code)| **row_code == code)
+            .map(|(row_idx, _)| row_idx)
+            .collect()
+    }
+}
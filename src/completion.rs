@@ -0,0 +1,384 @@
+//! The context-aware completion of the statements.
+
+use crate::{output::OutputMode, session::COMMANDS};
+
+/// The characters separating the words of a statement.
+const SEPARATORS: [char; 5] = [',', '=', '<', '>', ';'];
+
+/// The comparison operators of the filters.
+const OPERATORS: [&str; 3] = ["=", "<", ">"];
+
+/// The position in the statement grammar, telling what comes next.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Position {
+    /// The beginning of a statement.
+    Start,
+    /// After `EXPLAIN`.
+    Explain,
+    /// After `EXPLAIN ANALYZE`, a query is expected.
+    Query,
+    /// After `CREATE`.
+    Create,
+    /// After `CREATE INDEX`.
+    Index,
+    /// After `CREATE INDEX ON`, a column is expected.
+    IndexColumn,
+    /// After the indexed column.
+    AfterIndexColumn,
+    /// After `CREATE INDEX ON column FROM`, a table is expected.
+    IndexTable,
+    /// After `PROJECT` or a comma in the projection.
+    ProjectColumn,
+    /// After a projected column.
+    AfterProjectColumn,
+    /// After `FROM`, a table is expected.
+    Table,
+    /// After the queried table.
+    AfterTable,
+    /// After `FILTER` or a comma between the filters.
+    FilterColumn,
+    /// After the filtered column, an operator is expected.
+    Operator,
+    /// After an operator, a value is expected.
+    Value,
+    /// After a complete filter.
+    AfterFilter,
+    /// After `ORDER`.
+    Order,
+    /// After `ORDER BY`, a column is expected.
+    OrderColumn,
+    /// After the column to order the rows by.
+    AfterOrderColumn,
+    /// After the sort order.
+    AfterSortOrder,
+    /// After `LIMIT`, a number is expected.
+    Limit,
+    /// After the number of rows.
+    AfterLimit,
+    /// After `INTO`, a file path is expected.
+    Into,
+    /// After the file path.
+    AfterInto,
+    /// After `FORMAT`, an output mode is expected.
+    Format,
+    /// Nothing can follow.
+    End,
+}
+
+impl Position {
+    /// Returns the position following the token.
+    fn next(self, token: &str) -> Self {
+        let keyword = token.to_ascii_uppercase();
+
+        match (self, keyword.as_str()) {
+            (Self::Start, "EXPLAIN") => Self::Explain,
+            (Self::Start | Self::Explain | Self::Query, "PROJECT") => Self::ProjectColumn,
+            (Self::Start, "CREATE") => Self::Create,
+            (Self::Explain, "ANALYZE") => Self::Query,
+            (Self::Create, "INDEX") => Self::Index,
+            (Self::Index, "ON") => Self::IndexColumn,
+            (Self::IndexColumn, _) => Self::AfterIndexColumn,
+            (Self::AfterIndexColumn, "FROM") => Self::IndexTable,
+            (Self::AfterProjectColumn, "FROM") => Self::Table,
+            (Self::Table, _) => Self::AfterTable,
+            (Self::ProjectColumn, _) => Self::AfterProjectColumn,
+            (Self::AfterProjectColumn, ",") => Self::ProjectColumn,
+            (Self::AfterProjectColumn | Self::AfterTable, "FILTER") => Self::FilterColumn,
+            (Self::FilterColumn, _) => Self::Operator,
+            (Self::Operator, _) if OPERATORS.contains(&token) => Self::Value,
+            (Self::Value, _) => Self::AfterFilter,
+            (Self::AfterFilter, ",") => Self::FilterColumn,
+            (Self::AfterFilter, "ORDER") => Self::Order,
+            (Self::Order, "BY") => Self::OrderColumn,
+            (Self::OrderColumn, _) => Self::AfterOrderColumn,
+            (Self::AfterOrderColumn, "ASC" | "DESC") => Self::AfterSortOrder,
+            (Self::AfterFilter | Self::AfterOrderColumn | Self::AfterSortOrder, "LIMIT") => {
+                Self::Limit
+            }
+            (Self::Limit, _) => Self::AfterLimit,
+            (
+                Self::AfterFilter
+                | Self::AfterOrderColumn
+                | Self::AfterSortOrder
+                | Self::AfterLimit,
+                "INTO",
+            ) => Self::Into,
+            (Self::Into, _) => Self::AfterInto,
+            (
+                Self::AfterFilter
+                | Self::AfterOrderColumn
+                | Self::AfterSortOrder
+                | Self::AfterLimit
+                | Self::AfterInto,
+                "FORMAT",
+            ) => Self::Format,
+            _ => Self::End,
+        }
+    }
+
+    /// Returns the keywords which may come next.
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::Start => &["PROJECT", "EXPLAIN", "CREATE"],
+            Self::Explain => &["ANALYZE", "PROJECT"],
+            Self::Query => &["PROJECT"],
+            Self::Create => &["INDEX"],
+            Self::Index => &["ON"],
+            Self::AfterIndexColumn => &["FROM"],
+            Self::AfterProjectColumn => &["FROM", "FILTER"],
+            Self::AfterTable => &["FILTER"],
+            Self::Operator => &OPERATORS,
+            Self::AfterFilter => &["ORDER", "LIMIT", "INTO", "FORMAT"],
+            Self::Order => &["BY"],
+            Self::AfterOrderColumn => &["ASC", "DESC", "LIMIT", "INTO", "FORMAT"],
+            Self::AfterSortOrder => &["LIMIT", "INTO", "FORMAT"],
+            Self::AfterLimit => &["INTO", "FORMAT"],
+            Self::AfterInto => &["FORMAT"],
+            _ => &[],
+        }
+    }
+
+    /// Returns [`true`] if a table name may come next.
+    fn expects_table(self) -> bool {
+        matches!(self, Self::IndexTable | Self::Table)
+    }
+
+    /// Returns [`true`] if a column name may come next.
+    fn expects_column(self) -> bool {
+        matches!(
+            self,
+            Self::IndexColumn | Self::ProjectColumn | Self::FilterColumn | Self::OrderColumn
+        )
+    }
+}
+
+/// Splits the statement into the words and the separators.
+fn tokenize(input: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut word_start = 0;
+
+    for (i, c) in input.char_indices() {
+        if c.is_whitespace() || SEPARATORS.contains(&c) {
+            tokens.push(&input[word_start..i]);
+            tokens.push(&input[i..i + c.len_utf8()]);
+            word_start = i + c.len_utf8();
+        }
+    }
+    tokens.push(&input[word_start..]);
+
+    tokens.retain(|token| !token.trim().is_empty());
+    tokens
+}
+
+/// Returns the candidates starting with the prefix, ignoring the case.
+fn matching<'a>(candidates: impl IntoIterator<Item = &'a str>, prefix: &str) -> Vec<String> {
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            candidate
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Returns the completions of the meta-command or its argument.
+fn complete_command(command: &str, prefix: &str, tables: &[String]) -> Vec<String> {
+    match command {
+        "" => matching(COMMANDS.iter().map(|(name, _)| *name), prefix),
+        ".schema" => matching(tables.iter().map(String::as_str), prefix),
+        ".mode" => matching(
+            OutputMode::ALL
+                .iter()
+                .filter(|mode| !mode.is_binary())
+                .map(OutputMode::as_str),
+            prefix,
+        ),
+        ".timer" => matching(["on", "off"], prefix),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the completions of the word ending at the position in the
+/// line: the keywords, the meta-commands, and the table and the column
+/// names which are valid there.
+///
+/// The first element of the tuple is the position the completed word
+/// starts at.
+pub fn complete(
+    line: &str,
+    pos: usize,
+    columns: &[String],
+    tables: &[String],
+) -> (usize, Vec<String>) {
+    let line = &line[..pos];
+    let start = line
+        .rfind(|c: char| c.is_whitespace() || SEPARATORS.contains(&c))
+        .map_or(0, |i| {
+            i + line[i..].chars().next().map_or(1, char::len_utf8)
+        });
+    let prefix = &line[start..];
+
+    if crate::session::is_command(line) {
+        let mut words = line[..start].split_whitespace();
+        let command = words.next().unwrap_or_default();
+
+        // Only the first argument is completed.
+        if words.next().is_some() {
+            return (start, Vec::new());
+        }
+
+        return (start, complete_command(command, prefix, tables));
+    }
+
+    // Only the current statement matters.
+    let statement = line[..start].rsplit(';').next().unwrap_or_default();
+    let position = tokenize(statement)
+        .into_iter()
+        .fold(Position::Start, Position::next);
+
+    let keywords = matching(position.keywords().iter().copied(), prefix).into_iter();
+
+    let formats = OutputMode::ALL
+        .iter()
+        .filter(|_| position == Position::Format)
+        .map(OutputMode::as_str)
+        .filter(|format| format.starts_with(prefix))
+        .map(str::to_owned);
+
+    let tables = tables
+        .iter()
+        .filter(|_| position.expects_table())
+        .filter(|table| table.starts_with(prefix))
+        .cloned();
+
+    let columns = columns
+        .iter()
+        .filter(|_| position.expects_column())
+        .filter(|column| column.starts_with(prefix))
+        .cloned();
+
+    (
+        start,
+        keywords
+            .chain(formats)
+            .chain(tables)
+            .chain(columns)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables() -> Vec<String> {
+        vec!["data".to_string(), "other".to_string()]
+    }
+
+    fn columns() -> Vec<String> {
+        vec!["col1".to_string(), "col2".to_string(), "name".to_string()]
+    }
+
+    #[test]
+    fn completes_keywords() {
+        assert_eq!(
+            complete("PR", 2, &columns(), &tables()),
+            (0, vec!["PROJECT".to_string()])
+        );
+        assert_eq!(
+            complete("explain ", 8, &columns(), &tables()).1,
+            vec!["ANALYZE".to_string(), "PROJECT".to_string()]
+        );
+        assert_eq!(
+            complete("PROJECT col1 F", 14, &columns(), &tables()),
+            (13, vec!["FROM".to_string(), "FILTER".to_string()])
+        );
+        assert_eq!(
+            complete("PROJECT col1 FILTER col2 ", 25, &columns(), &tables()).1,
+            vec!["=".to_string(), "<".to_string(), ">".to_string()]
+        );
+        assert_eq!(
+            complete("PROJECT col1 FILTER col2 > 5 ", 29, &columns(), &tables()).1,
+            vec![
+                "ORDER".to_string(),
+                "LIMIT".to_string(),
+                "INTO".to_string(),
+                "FORMAT".to_string()
+            ]
+        );
+        assert_eq!(
+            complete(
+                "PROJECT col1 FILTER col2 > 5 LIMIT 1 FORMAT n",
+                45,
+                &columns(),
+                &tables()
+            ),
+            (44, vec!["ndjson".to_string()])
+        );
+        assert_eq!(
+            complete(
+                "PROJECT col1 FILTER col2 > 5 INTO 'out.csv' ",
+                44,
+                &columns(),
+                &tables()
+            )
+            .1,
+            vec!["FORMAT".to_string()]
+        );
+        assert_eq!(
+            complete("CREATE INDEX ON col1 ", 21, &columns(), &tables()).1,
+            vec!["FROM".to_string()]
+        );
+    }
+
+    #[test]
+    fn completes_tables_and_commands() {
+        assert_eq!(
+            complete("PROJECT col1 FROM o", 19, &columns(), &tables()),
+            (18, vec!["other".to_string()])
+        );
+        assert_eq!(
+            complete("PROJECT col1 FROM data ", 23, &columns(), &tables()).1,
+            vec!["FILTER".to_string()]
+        );
+        assert_eq!(
+            complete(".sc", 3, &columns(), &tables()),
+            (0, vec![".schema".to_string()])
+        );
+        assert_eq!(
+            complete(".schema d", 9, &columns(), &tables()),
+            (8, vec!["data".to_string()])
+        );
+        assert_eq!(
+            complete(".timer o", 8, &columns(), &tables()).1,
+            vec!["on".to_string(), "off".to_string()]
+        );
+    }
+
+    #[test]
+    fn completes_columns() {
+        assert_eq!(
+            complete("PROJECT c", 9, &columns(), &tables()),
+            (8, vec!["col1".to_string(), "col2".to_string()])
+        );
+        assert_eq!(
+            complete("PROJECT col1,n", 14, &columns(), &tables()),
+            (13, vec!["name".to_string()])
+        );
+        assert_eq!(
+            complete("PROJECT col1 FILTER col2=5,", 27, &columns(), &tables()).1,
+            columns()
+        );
+        assert_eq!(
+            complete("CREATE INDEX ON ", 16, &columns(), &tables()).1,
+            columns()
+        );
+        assert!(
+            complete("PROJECT col1 FILTER col2 = ", 27, &columns(), &tables())
+                .1
+                .is_empty()
+        );
+    }
+}
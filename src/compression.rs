@@ -0,0 +1,157 @@
+//! The transparent decompression of the compressed files.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+
+/// The compression of a file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// The file is not compressed.
+    None,
+    /// The gzip compression, `.gz`.
+    Gzip,
+    /// The Zstandard compression, `.zst`.
+    Zstd,
+    /// The bzip2 compression, `.bz2`.
+    Bzip2,
+}
+
+impl Compression {
+    /// All the compressions.
+    const ALL: [Compression; 3] = [Compression::Gzip, Compression::Zstd, Compression::Bzip2];
+
+    /// Returns the bytes the compressed data starts with.
+    fn magic(&self) -> &'static [u8] {
+        match self {
+            Compression::None => &[],
+            Compression::Gzip => &[0x1f, 0x8b],
+            Compression::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+            Compression::Bzip2 => b"BZh",
+        }
+    }
+
+    /// Returns the extension of the compressed files.
+    fn extension(&self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+            Compression::Bzip2 => "bz2",
+        }
+    }
+
+    /// Returns the compression the data starts with the magic bytes of.
+    pub fn from_magic(data: &[u8]) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|compression| data.starts_with(compression.magic()))
+            .unwrap_or(Compression::None)
+    }
+
+    /// Returns the compression of the file by its extension.
+    pub fn from_path(path: &str) -> Self {
+        let extension = Path::new(path)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+
+        Self::ALL
+            .into_iter()
+            .find(|compression| extension.as_deref() == Some(compression.extension()))
+            .unwrap_or(Compression::None)
+    }
+
+    /// Wraps the reader into the one decompressing the data.
+    pub fn decoder<'a>(&self, reader: impl BufRead + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Compression::None => Box::new(reader),
+            Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+            Compression::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+            Compression::Bzip2 => Box::new(MultiBzDecoder::new(reader)),
+        })
+    }
+}
+
+/// Returns the path without the extension of the compressed files, if
+/// any.
+pub fn strip_extension(path: &str) -> &str {
+    match Compression::from_path(path) {
+        Compression::None => path,
+        compression => &path[..path.len() - compression.extension().len() - 1],
+    }
+}
+
+/// Wraps the reader into the one decompressing the data, detecting the
+/// compression by the magic bytes the data starts with.
+pub fn decompress<'a>(reader: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+    decompress_or(reader, Compression::None)
+}
+
+/// Opens the file for reading, decompressing it on the fly. The
+/// compression is detected by the magic bytes the file starts with, or
+/// by its extension if the file is too short to tell.
+pub fn open(path: &str) -> io::Result<Box<dyn Read>> {
+    decompress_or(File::open(path)?, Compression::from_path(path))
+}
+
+/// Wraps the reader into the one decompressing the data, falling back to
+/// the given compression if the data is too short to tell by the magic
+/// bytes.
+fn decompress_or<'a>(
+    reader: impl Read + 'a,
+    fallback: Compression,
+) -> io::Result<Box<dyn Read + 'a>> {
+    let mut reader = BufReader::new(reader);
+    let compression = match Compression::from_magic(reader.fill_buf()?) {
+        Compression::None if reader.buffer().len() < 4 => fallback,
+        compression => compression,
+    };
+
+    compression.decoder(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    const DATA: &[u8] = b"col1,col2\n1,a\n2,b\n";
+
+    fn read(data: &[u8]) -> Vec<u8> {
+        let mut decompressed = Vec::new();
+        decompress(data)
+            .unwrap()
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        decompressed
+    }
+
+    #[test]
+    fn decompresses() {
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gzip.write_all(DATA).unwrap();
+        assert_eq!(read(&gzip.finish().unwrap()), DATA);
+
+        assert_eq!(read(&zstd::encode_all(DATA, 1).unwrap()), DATA);
+
+        let mut bzip2 = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::fast());
+        bzip2.write_all(DATA).unwrap();
+        assert_eq!(read(&bzip2.finish().unwrap()), DATA);
+
+        assert_eq!(read(DATA), DATA);
+    }
+
+    #[test]
+    fn detects_compression_by_extension() {
+        assert_eq!(Compression::from_path("data.csv.GZ"), Compression::Gzip);
+        assert_eq!(Compression::from_path("data.csv.zst"), Compression::Zstd);
+        assert_eq!(Compression::from_path("data.csv"), Compression::None);
+        assert_eq!(strip_extension("dir/data.csv.bz2"), "dir/data.csv");
+    }
+}
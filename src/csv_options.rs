@@ -0,0 +1,140 @@
+//! The dialect of the CSV files.
+
+use csv::{ReaderBuilder, Trim};
+
+/// The options of reading a CSV file: its dialect and whether it has a
+/// header.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The character separating the fields.
+    delimiter: u8,
+    /// The character quoting the fields.
+    quote: u8,
+    /// The character escaping the quotes within the quoted fields, if
+    /// the quotes are not escaped by doubling them.
+    escape: Option<u8>,
+    /// The character starting the lines which are skipped, if any.
+    comment: Option<u8>,
+    /// Whether the first line holds the column names. Otherwise, the
+    /// columns are named `c1`, `c2` and so on.
+    has_headers: bool,
+    /// Whether the whitespace around the fields is removed.
+    trim: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            comment: None,
+            has_headers: true,
+            trim: false,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Creates the options of the comma-separated files with a header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the character separating the fields.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the character quoting the fields.
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the character escaping the quotes within the quoted fields.
+    pub fn escape(mut self, escape: Option<u8>) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// Sets the character starting the lines which are skipped.
+    pub fn comment(mut self, comment: Option<u8>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Sets whether the first line holds the column names.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Sets whether the whitespace around the fields is removed.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Returns [`true`] if the first line holds the column names.
+    pub fn headers(&self) -> bool {
+        self.has_headers
+    }
+
+    /// Returns the builder of the readers of the files in the dialect.
+    /// The header is always read as a record, so that the number of the
+    /// columns is known when there is no header.
+    pub fn reader_builder(&self) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .escape(self.escape)
+            .double_quote(self.escape.is_none())
+            .comment(self.comment)
+            .has_headers(false)
+            .trim(if self.trim { Trim::All } else { Trim::None });
+        builder
+    }
+}
+
+impl std::fmt::Display for CsvOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quoted = |c: u8| match c {
+            b'"' => "'\"'".to_string(),
+            c => format!("'{}'", (c as char).escape_default()),
+        };
+
+        write!(
+            f,
+            "delimiter {}, quote {}",
+            quoted(self.delimiter),
+            quoted(self.quote)
+        )?;
+
+        if let Some(escape) = self.escape {
+            write!(f, ", escape {}", quoted(escape))?;
+        }
+
+        if let Some(comment) = self.comment {
+            write!(f, ", comment {}", quoted(comment))?;
+        }
+
+        write!(
+            f,
+            ", {}",
+            if self.has_headers {
+                "header"
+            } else {
+                "no header"
+            }
+        )?;
+
+        if self.trim {
+            write!(f, ", trimmed")?;
+        }
+
+        Ok(())
+    }
+}
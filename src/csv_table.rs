@@ -1,59 +1,275 @@
 //! A table abstraction using CSV.
 
-use csv::Reader;
-use std::{collections::HashMap, error::Error};
+use std::{collections::HashMap, error::Error, io::Read, ops::Range};
 
 use crate::{
-    filter::{ApplyTableFilterByValue, FilterColumns, FilterQueryIterator},
-    table::{AsTable, ColumnValue},
+    column::Column,
+    compression,
+    csv_options::CsvOptions,
+    error::Error as CrateError,
+    filter::{ApplyTableFilterByValue, FilterColumns, FilterQueryIterator, Operation},
+    index::{AccessPath, ColumnIndex},
+    output::{self, OutputMode},
+    result_set::{ResultSet, ResultStream},
+    table::{AsTable, ColumnType, ColumnValue},
+    zone_map::{ZoneMap, BLOCK_SIZE},
 };
 
 #[derive(Debug)]
 pub struct CsvTable {
-    data: HashMap<String, Vec<ColumnValue>>,
+    /// The column names in the order of the CSV header.
+    columns: Vec<String>,
+    data: HashMap<String, Column>,
+    /// The secondary indexes, by column name.
+    indexes: HashMap<String, ColumnIndex>,
+    /// The zone maps of every column, by column name.
+    zone_maps: HashMap<String, Vec<ZoneMap>>,
+    /// The type of every column, if all its values are of the same type.
+    column_types: HashMap<String, Option<ColumnType>>,
+    /// The dialect of the file the table was loaded from.
+    dialect: CsvOptions,
 }
 
 impl CsvTable {
     // Load CSV data into memory
     pub fn from_csv(file_path: &str) -> Result<Self, Box<dyn Error>> {
-        let mut rdr = Reader::from_path(file_path)?;
-        let headers: Vec<String> = rdr.headers()?.iter().map(|h| h.to_string()).collect();
-        let mut data = HashMap::new();
+        Self::from_csv_with_options(file_path, &CsvOptions::default())
+    }
+
+    /// Loads the CSV file of the dialect into memory, decompressing it if
+    /// it is compressed with gzip, zstd or bzip2. Without a header,
+    /// the columns are named `c1`, `c2` and so on.
+    pub fn from_csv_with_options(
+        file_path: &str,
+        options: &CsvOptions,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::from_reader(compression::open(file_path)?, options)
+    }
+
+    /// Loads the CSV data of the dialect read from the reader into memory,
+    /// such as the standard input or a byte slice. Without a header, the
+    /// columns are named `c1`, `c2` and so on.
+    pub fn from_reader<R: Read>(reader: R, options: &CsvOptions) -> Result<Self, Box<dyn Error>> {
+        let mut records = options.reader_builder().from_reader(reader).into_records();
+        let first = records.next().transpose()?.unwrap_or_default();
+
+        let (headers, first): (Vec<String>, _) = if options.headers() {
+            (first.iter().map(|h| h.to_string()).collect(), None)
+        } else {
+            (
+                (1..=first.len()).map(|i| format!("c{i}")).collect(),
+                Some(first),
+            )
+        };
+        let mut values: Vec<Vec<ColumnValue>> = vec![Vec::new(); headers.len()];
 
-        for result in rdr.records() {
+        for result in first.into_iter().map(Ok).chain(records) {
             let record = result?;
             for (i, value) in record.iter().enumerate() {
-                data.entry(headers[i].clone())
-                    .or_insert_with(Vec::new)
-                    .push(value.parse()?);
+                values[i].push(value.parse()?);
             }
         }
 
-        Ok(CsvTable { data })
+        let mut table = Self::from_columns(headers.into_iter().zip(values));
+        table.dialect = *options;
+
+        Ok(table)
     }
 
-    /// Queries the table with a filter and prints out the result to
-    /// the stdout.
-    pub fn query<F, E>(&self, filter_columns: F) -> crate::error::Result<(), E>
-    where
-        FilterColumns: TryFrom<F, Error = E>,
-    {
-        let filter_columns = FilterColumns::try_from(filter_columns)?;
+    /// Returns the dialect of the file the table was loaded from.
+    pub fn dialect(&self) -> &CsvOptions {
+        &self.dialect
+    }
 
-        self.apply_filter(&filter_columns).for_each(|row| {
-            for (col_name, value) in row {
-                print!("{}: {} ", col_name, value);
+    /// Creates a table from the named column values, choosing the
+    /// encoding of each column and building its zone maps. The repeated
+    /// names are suffixed with `_2`, `_3` and so on.
+    pub fn from_columns(columns: impl IntoIterator<Item = (String, Vec<ColumnValue>)>) -> Self {
+        let mut names = Vec::new();
+        let mut data = HashMap::new();
+        let mut zone_maps = HashMap::new();
+        let mut column_types = HashMap::new();
+
+        for (name, values) in columns {
+            // The columns are looked up by name, so a repeated name would
+            // hide all but one of them.
+            let mut unique = name.clone();
+            for n in 2.. {
+                if !data.contains_key(&unique) {
+                    break;
+                }
+                unique = format!("{name}_{n}");
             }
-            println!()
-        });
+            let name = unique;
+
+            let column = Column::from_values(values);
+            names.push(name.clone());
+            zone_maps.insert(name.clone(), ZoneMap::build(&column));
+            column_types.insert(name.clone(), column.get_type());
+            data.insert(name, column);
+        }
+
+        CsvTable {
+            columns: names,
+            data,
+            indexes: HashMap::new(),
+            zone_maps,
+            column_types,
+            dialect: CsvOptions::default(),
+        }
+    }
+
+    /// Builds an index on the column, replacing the existing one, if
+    /// any.
+    pub fn create_index(&mut self, column: &str) -> crate::error::Result {
+        let values = self
+            .data
+            .get(column)
+            .ok_or_else(|| CrateError::ColumnNotFound(column.to_owned()))?;
+
+        self.indexes
+            .insert(column.to_owned(), ColumnIndex::build(values.iter())?);
 
         Ok(())
     }
+
+    /// Returns [`true`] if the column is indexed.
+    pub fn has_index(&self, column: &str) -> bool {
+        self.indexes.contains_key(column)
+    }
+
+    /// Returns the access path visiting only the blocks whose zone maps
+    /// may satisfy all the filters.
+    fn pruned_access_path(&self, filter: &FilterColumns) -> AccessPath {
+        let row_count = self.row_count();
+        let total_blocks = row_count.div_ceil(BLOCK_SIZE);
+
+        let rows: Vec<Range<usize>> = (0..total_blocks)
+            .filter(|block| {
+                filter.filters.iter().all(|(column, filter)| {
+                    self.zone_maps
+                        .get(column)
+                        .and_then(|zone_maps| zone_maps.get(*block))
+                        .is_none_or(|zone_map| zone_map.may_match(filter))
+                })
+            })
+            .map(|block| block * BLOCK_SIZE..((block + 1) * BLOCK_SIZE).min(row_count))
+            .collect();
+
+        let skipped_blocks = total_blocks - rows.len();
+
+        if skipped_blocks == 0 {
+            return AccessPath::Full;
+        }
+
+        AccessPath::Pruned {
+            rows,
+            skipped_blocks,
+            total_blocks,
+        }
+    }
+
+    /// Returns the number of rows in the table.
+    pub fn row_count(&self) -> usize {
+        self.data.values().next().map_or(0, Column::len)
+    }
+
+    /// Queries the table with a filter and returns the result.
+    pub fn execute<F>(&self, filter_columns: F) -> crate::error::Result<ResultSet>
+    where
+        FilterColumns: TryFrom<F>,
+        CrateError: From<<FilterColumns as TryFrom<F>>::Error>,
+    {
+        ResultSet::execute(&FilterColumns::try_from(filter_columns)?, self)
+    }
+
+    /// Queries the table with a filter and prints out the result to
+    /// the stdout in the output mode.
+    pub fn query<F>(&self, filter_columns: F, mode: OutputMode) -> crate::error::Result
+    where
+        FilterColumns: TryFrom<F>,
+        CrateError: From<<FilterColumns as TryFrom<F>>::Error>,
+    {
+        output::print(
+            mode,
+            ResultStream::execute(&FilterColumns::try_from(filter_columns)?, self)?,
+        )
+    }
 }
 
 impl ApplyTableFilterByValue<'_> for CsvTable {
-    fn apply_filter(&self, filter: &FilterColumns) -> FilterQueryIterator {
-        FilterQueryIterator::new(Box::new(self.get_rows()), filter.clone())
+    fn apply_filter(&self, filter: &FilterColumns) -> FilterQueryIterator<'_> {
+        let access_path = self.access_path(filter);
+
+        let row_ids: Box<dyn Iterator<Item = usize>> = match &access_path {
+            AccessPath::Full => Box::new(0..self.row_count()),
+            AccessPath::Pruned { rows, .. } => Box::new(rows.clone().into_iter().flatten()),
+            AccessPath::Index { column, filter } => {
+                Box::new(self.indexes[column].lookup(filter).into_iter())
+            }
+            AccessPath::Dictionary { column, filter } => match &self.data[column] {
+                Column::Dictionary(dictionary) => {
+                    Box::new(dictionary.rows_equal_to(&filter.value).into_iter())
+                }
+                // The access path is only chosen for the dictionary-encoded
+                // columns, and its filter is not evaluated again.
+                Column::Plain(_) => unreachable!("dictionary scan on the plain column {column}"),
+            },
+        };
+
+        let rows = Box::new(row_ids.map(|row_idx| self.get_row(row_idx)));
+
+        // The filter satisfied by the access path needn't be evaluated
+        // again for every row.
+        let mut residual = filter.clone();
+        if let Some(column) = access_path.satisfied_column() {
+            residual.filters.remove(column);
+        }
+
+        FilterQueryIterator::new(rows, residual)
+    }
+
+    /// Chooses the way the rows are accessed for the filter.
+    ///
+    /// An indexed equality filter is preferred over an indexed range
+    /// filter, as it is usually the more selective one. Without an
+    /// index, an equality filter on a dictionary-encoded column is used
+    /// to compare the dictionary codes instead of the strings. Otherwise
+    /// the blocks whose zone maps cannot satisfy the filters are skipped.
+    fn access_path(&self, filter: &FilterColumns) -> AccessPath {
+        let mut candidates: Vec<_> = filter
+            .filters
+            .iter()
+            .filter(|(column, _)| self.has_index(column))
+            .collect();
+
+        candidates.sort_by_key(|(column, filter)| (filter.operation != Operation::Equal, *column));
+
+        if let Some((column, filter)) = candidates.first() {
+            return AccessPath::Index {
+                column: column.to_string(),
+                filter: (*filter).clone(),
+            };
+        }
+
+        let mut candidates: Vec<_> = filter
+            .filters
+            .iter()
+            .filter(|(column, filter)| {
+                filter.operation == Operation::Equal
+                    && matches!(self.data.get(*column), Some(Column::Dictionary(_)))
+            })
+            .collect();
+
+        candidates.sort_by_key(|(column, _)| *column);
+
+        match candidates.first() {
+            Some((column, filter)) => AccessPath::Dictionary {
+                column: column.to_string(),
+                filter: (*filter).clone(),
+            },
+            None => self.pruned_access_path(filter),
+        }
     }
 }
 
@@ -62,35 +278,18 @@ impl AsTable for CsvTable {
         "CSV Table (in-memory)"
     }
 
-    fn get_columns(&self) -> impl Iterator<Item = (&str, &[ColumnValue])> {
-        self.data
+    fn get_columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.columns
             .iter()
-            .map(|(name, values)| (name.as_str(), values.as_slice()))
+            .map(|name| (name.as_str(), &self.data[name]))
     }
 
     fn get_column_names(&self) -> impl Iterator<Item = &String> {
-        self.data.keys()
+        self.columns.iter()
     }
 
-    // Iterator over rows without collecting into vectors
-    fn get_rows(&self) -> Box<dyn Iterator<Item = HashMap<String, &ColumnValue>> + '_> {
-        // Assume all columns have the same number of rows, get the number of rows from the first column
-        let num_rows = if let Some(first_column) = self.data.values().next() {
-            first_column.len()
-        } else {
-            0
-        };
-
-        // Return a row iterator using indexing
-        Box::new((0..num_rows).map(move |row_idx| {
-            let mut row = HashMap::new();
-            for (col_name, col_values) in &self.data {
-                if let Some(value) = col_values.get(row_idx) {
-                    row.insert(col_name.clone(), value);
-                }
-            }
-            row
-        }))
+    fn get_column_type(&self, name: &str) -> Option<ColumnType> {
+        self.column_types.get(name).copied().flatten()
     }
 }
 
@@ -98,7 +297,10 @@ impl AsTable for CsvTable {
 mod tests {
     use std::collections::HashMap;
 
-    use crate::table::{ColumnType, IntegerColumnType, StringColumnType};
+    use crate::{
+        plan::LogicalPlan,
+        table::{IntegerColumnType, StringColumnType},
+    };
 
     use super::*;
 
@@ -116,19 +318,16 @@ mod tests {
             ),
         ];
 
-        let mut table = CsvTable {
-            data: HashMap::new(),
-        };
+        let mut columns: Vec<(String, Vec<ColumnValue>)> = Vec::new();
 
         for (col_name, value) in data {
-            table
-                .data
-                .entry(col_name.to_string())
-                .or_default()
-                .push(value);
+            match columns.iter_mut().find(|(name, _)| name == col_name) {
+                Some((_, values)) => values.push(value),
+                None => columns.push((col_name.to_string(), vec![value])),
+            }
         }
 
-        table
+        CsvTable::from_columns(columns)
     }
 
     #[test]
@@ -136,16 +335,16 @@ mod tests {
         let table = create_csv_table();
         assert_eq!(table.get_name(), "CSV Table (in-memory)");
 
-        let columns: Vec<(&str, &[ColumnValue])> = table.get_columns().collect();
+        let columns: Vec<(&str, &Column)> = table.get_columns().collect();
         assert_eq!(columns.len(), 2);
         assert_eq!(columns[0].0, "col1");
         assert_eq!(columns[0].1.len(), 2);
-        assert_eq!(columns[0].1[0].get_type(), ColumnType::Integer);
-        assert_eq!(columns[0].1[1].get_type(), ColumnType::Integer);
+        assert_eq!(columns[0].1.get(0).unwrap().get_type(), ColumnType::Integer);
+        assert_eq!(columns[0].1.get(1).unwrap().get_type(), ColumnType::Integer);
         assert_eq!(columns[1].0, "col2");
         assert_eq!(columns[1].1.len(), 2);
-        assert_eq!(columns[1].1[0].get_type(), ColumnType::String);
-        assert_eq!(columns[1].1[1].get_type(), ColumnType::String);
+        assert_eq!(columns[1].1.get(0).unwrap().get_type(), ColumnType::String);
+        assert_eq!(columns[1].1.get(1).unwrap().get_type(), ColumnType::String);
 
         let column_names: Vec<&String> = table.get_column_names().collect();
         assert_eq!(column_names.len(), 2);
@@ -153,6 +352,68 @@ mod tests {
         assert_eq!(column_names[1], "col2");
     }
 
+    #[test]
+    fn names_repeated_columns() {
+        let table =
+            CsvTable::from_reader("a,a,b,a\n1,2,3,4\n".as_bytes(), &CsvOptions::default()).unwrap();
+
+        assert_eq!(
+            table.get_column_names().collect::<Vec<_>>(),
+            vec!["a", "a_2", "b", "a_3"]
+        );
+        assert_eq!(
+            table.data["a_2"].get(0),
+            Some(&ColumnValue::Integer(IntegerColumnType(2)))
+        );
+    }
+
+    #[test]
+    fn load_compressed() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("prisma-test-{}.csv.gz", std::process::id()));
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gzip.write_all(b"col1,col2\n1,a\n2,b\n").unwrap();
+        std::fs::write(&path, gzip.finish().unwrap()).unwrap();
+
+        let table = CsvTable::from_csv(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let table = table.unwrap();
+
+        assert_eq!(
+            table.get_column_names().collect::<Vec<_>>(),
+            vec!["col1", "col2"]
+        );
+        assert_eq!(table.get_column_type("col1"), Some(ColumnType::Integer));
+    }
+
+    #[test]
+    fn load_with_options() {
+        let data = b"# exported\n 1 |'a|b'\n2| c \n";
+        let options = CsvOptions::new()
+            .delimiter(b'|')
+            .quote(b'\'')
+            .comment(Some(b'#'))
+            .has_headers(false)
+            .trim(true);
+        let table = CsvTable::from_reader(data.as_slice(), &options).unwrap();
+
+        assert_eq!(
+            table.get_column_names().collect::<Vec<_>>(),
+            vec!["c1", "c2"]
+        );
+        assert_eq!(table.get_column_type("c1"), Some(ColumnType::Integer));
+        let c2 = table.get_columns().last().unwrap().1;
+        assert_eq!(
+            c2.get(0),
+            Some(&ColumnValue::String(StringColumnType("a|b".to_string())))
+        );
+        assert_eq!(
+            c2.get(1),
+            Some(&ColumnValue::String(StringColumnType("c".to_string())))
+        );
+    }
+
     #[test]
     fn get_rows() {
         use crate::table::ColumnType;
@@ -197,6 +458,7 @@ mod tests {
             )]
             .into_iter()
             .collect(),
+            ..Default::default()
         };
 
         let filtered_iter = table.apply_filter(&filter_columns);
@@ -207,4 +469,227 @@ mod tests {
         assert_eq!(filtered_rows[0]["col1"].get_type(), ColumnType::Integer);
         assert_eq!(filtered_rows[0]["col1"].as_string(), None);
     }
+
+    #[test]
+    fn filter_with_index() {
+        use crate::filter::{FilterByValue, Operation};
+
+        let mut table = create_csv_table();
+        assert!(table.create_index("col3").is_err());
+        table.create_index("col1").unwrap();
+
+        let filter_columns = FilterColumns {
+            output_columns: vec!["col2".to_string()],
+            filters: vec![(
+                "col1".to_string(),
+                FilterByValue {
+                    operation: Operation::GreaterThan,
+                    value: ColumnValue::Integer(IntegerColumnType(1)),
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            table.access_path(&filter_columns),
+            AccessPath::Index { ref column, .. } if column == "col1"
+        ));
+
+        let filtered_rows: Vec<HashMap<String, &ColumnValue>> =
+            table.apply_filter(&filter_columns).collect();
+
+        assert_eq!(filtered_rows.len(), 1);
+        assert_eq!(
+            filtered_rows[0]["col2"].as_string(),
+            Some(&StringColumnType("value2".to_string()))
+        );
+    }
+
+    #[test]
+    fn filter_with_dictionary() {
+        use crate::filter::{FilterByValue, Operation};
+
+        let table = CsvTable::from_columns([
+            (
+                "col1".to_string(),
+                (1..=20).map(|i| ColumnValue::Integer(i.into())).collect(),
+            ),
+            (
+                "col2".to_string(),
+                ["foo", "bar"]
+                    .into_iter()
+                    .cycle()
+                    .take(20)
+                    .map(|s| ColumnValue::String(s.into()))
+                    .collect(),
+            ),
+        ]);
+
+        assert!(matches!(table.data["col1"], Column::Plain(_)));
+        assert!(matches!(table.data["col2"], Column::Dictionary(_)));
+        let unique = (0..20)
+            .map(|i| ColumnValue::String(format!("v{i}").into()))
+            .collect();
+        assert!(matches!(Column::from_values(unique), Column::Plain(_)));
+
+        let filter_columns = FilterColumns {
+            output_columns: vec!["col1".to_string()],
+            filters: vec![(
+                "col2".to_string(),
+                FilterByValue {
+                    operation: Operation::Equal,
+                    value: ColumnValue::String("bar".into()),
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            table.access_path(&filter_columns),
+            AccessPath::Dictionary { ref column, .. } if column == "col2"
+        ));
+
+        let col1: Vec<_> = table
+            .apply_filter(&filter_columns)
+            .map(|row| row["col1"].as_integer().unwrap().0)
+            .collect();
+
+        assert_eq!(col1, (2..=20).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn filter_with_zone_maps() {
+        use crate::filter::{FilterByValue, Operation};
+
+        let row_count = BLOCK_SIZE * 3 + 1;
+        let table = CsvTable::from_columns([(
+            "col1".to_string(),
+            (0..row_count as i64)
+                .map(|i| ColumnValue::Integer(i.into()))
+                .collect(),
+        )]);
+
+        let filter_columns = FilterColumns {
+            output_columns: vec!["col1".to_string()],
+            filters: vec![(
+                "col1".to_string(),
+                FilterByValue {
+                    operation: Operation::GreaterThan,
+                    value: ColumnValue::Integer((BLOCK_SIZE as i64 * 3 - 1).into()),
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            table.access_path(&filter_columns),
+            AccessPath::Pruned {
+                skipped_blocks: 3,
+                total_blocks: 4,
+                ..
+            }
+        ));
+
+        let col1: Vec<_> = table
+            .apply_filter(&filter_columns)
+            .map(|row| row["col1"].as_integer().unwrap().0)
+            .collect();
+
+        assert_eq!(col1, vec![BLOCK_SIZE as i64 * 3]);
+    }
+
+    #[test]
+    fn plan_is_optimized() {
+        let table = create_csv_table();
+
+        let plan = LogicalPlan::optimized(
+            &FilterColumns::try_from(r#"PROJECT col1 FILTER col2 = "value1" LIMIT 1"#).unwrap(),
+            &table,
+        )
+        .unwrap();
+        let LogicalPlan::Limit { input, count: 1 } = plan else {
+            panic!("Expected a limit, got {plan:?}");
+        };
+        let LogicalPlan::Scan { columns, filters } = *input else {
+            panic!("Expected the filter to be pushed down into the scan");
+        };
+        assert_eq!(columns, vec!["col1".to_string()]);
+        assert!(filters.contains_key("col2"));
+
+        let plan = LogicalPlan::optimized(
+            &FilterColumns::try_from("PROJECT col1 FILTER col2 = 5").unwrap(),
+            &table,
+        )
+        .unwrap();
+        assert!(matches!(plan, LogicalPlan::Empty { .. }));
+
+        assert!(LogicalPlan::optimized(
+            &FilterColumns::try_from("PROJECT col3 FILTER col2 = 5").unwrap(),
+            &table
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn plan_sorts_and_limits() {
+        let table = create_csv_table();
+        let filter_columns =
+            FilterColumns::try_from("PROJECT col1 FILTER col1 > 0 ORDER BY col1 DESC LIMIT 1")
+                .unwrap();
+
+        let col1: Vec<_> = LogicalPlan::optimized(&filter_columns, &table)
+            .unwrap()
+            .execute(&table)
+            .map(|row| row["col1"].as_integer().unwrap().0)
+            .collect();
+
+        assert_eq!(col1, vec![2]);
+    }
+
+    #[test]
+    fn execute_returns_result_set() {
+        let table = create_csv_table();
+        let result = table
+            .execute("PROJECT col2, col1 FILTER col1 > 0 ORDER BY col1 DESC")
+            .unwrap();
+
+        assert_eq!(result.columns(), ["col2".to_string(), "col1".to_string()]);
+        assert_eq!(
+            result.column_types(),
+            [Some(ColumnType::String), Some(ColumnType::Integer)]
+        );
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[0],
+            [
+                ColumnValue::String(StringColumnType("value2".to_string())),
+                ColumnValue::Integer(IntegerColumnType(2)),
+            ]
+        );
+        assert_eq!(
+            result.value(1, "col1"),
+            Some(&ColumnValue::Integer(IntegerColumnType(1)))
+        );
+    }
+
+    #[test]
+    fn explain_analyze() {
+        let mut table = create_csv_table();
+        table.create_index("col1").unwrap();
+
+        let filter_columns = FilterColumns::try_from("PROJECT col2 FILTER col1 = 2").unwrap();
+        let explanation = crate::explain::explain(&filter_columns, &table, true)
+            .unwrap()
+            .to_string();
+
+        assert!(explanation.contains("Scan [col2] filters: col1 = 2 (rows: 1, scanned: 1"));
+        assert!(explanation.contains("Access path: Index scan on col1 (col1 = 2)"));
+        assert!(explanation.contains("Returned 1 rows"));
+    }
 }
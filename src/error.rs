@@ -47,6 +47,14 @@ pub enum Error {
     ValueParse(String),
     /// A filter error.
     Filter(FilterError),
+    /// The referenced column does not exist in the table.
+    ColumnNotFound(String),
+    /// The referenced table is not loaded.
+    TableNotFound(String),
+    /// The meta-command is unknown or malformed.
+    InvalidCommand(String),
+    /// The statement was cancelled before it completed.
+    Cancelled,
     /// Any other error type.
     Other(Box<dyn std::error::Error>),
 }
@@ -56,6 +64,10 @@ impl std::fmt::Display for Error {
         match self {
             Self::ValueParse(e) => write!(f, "Value parse: {e}"),
             Self::Filter(e) => write!(f, "Filter: {e}"),
+            Self::ColumnNotFound(name) => write!(f, "Column not found: {name}"),
+            Self::TableNotFound(name) => write!(f, "Table not found: {name}"),
+            Self::InvalidCommand(e) => write!(f, "Invalid command: {e}"),
+            Self::Cancelled => write!(f, "The statement was cancelled"),
             Self::Other(e) => write!(f, "Other: {e}"),
         }
     }
@@ -69,6 +81,12 @@ impl From<FilterError> for Error {
     }
 }
 
+impl From<std::convert::Infallible> for Error {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
 impl From<Box<dyn std::error::Error>> for Error {
     fn from(e: Box<dyn std::error::Error>) -> Self {
         Self::Other(e)
@@ -81,12 +99,42 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<arrow::error::ArrowError> for Error {
+    fn from(e: arrow::error::ArrowError) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
+impl From<calamine::Error> for Error {
+    fn from(e: calamine::Error) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
 impl From<csv::Error> for Error {
     fn from(e: csv::Error) -> Self {
         Self::Other(Box::new(e))
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(e: parquet::errors::ParquetError) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Other(Box::new(e))
+    }
+}
+
 impl From<ParseIntError> for Error {
     fn from(e: ParseIntError) -> Self {
         Self::ValueParse(e.to_string())
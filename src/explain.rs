@@ -0,0 +1,150 @@
+//! The description of how the queries are executed.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    cancel,
+    error::{Error, Result},
+    filter::{ApplyTableFilterByValue, FilterColumns},
+    index::AccessPath,
+    memory, optimizer,
+    plan::{LogicalPlan, NodeStats},
+    table::AsTable,
+};
+
+/// The description of how a query is executed against a table.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// The parsed query.
+    query: FilterColumns,
+    /// The plan before the optimization.
+    plan: LogicalPlan,
+    /// The plan which is executed.
+    optimized: LogicalPlan,
+    /// The way the table rows are accessed, if the table is read at all.
+    access_path: Option<AccessPath>,
+    /// The statistics of the execution, if the query was run.
+    analysis: Option<Analysis>,
+}
+
+/// The statistics of a query execution.
+#[derive(Debug, Clone)]
+struct Analysis {
+    /// The statistics of every plan node, from the root node down.
+    stats: Vec<NodeStats>,
+    /// The number of rows returned by the query.
+    row_count: usize,
+    /// The time spent running the query.
+    total_time: Duration,
+    /// The peak memory allocated while running the query.
+    peak_memory: usize,
+}
+
+/// Describes how the query is executed against the table.
+///
+/// When `analyze` is [`true`], the query is also run to collect the
+/// number of rows and the time spent in every plan node, as well as the
+/// peak memory usage.
+pub fn explain<'a, T>(query: &FilterColumns, table: &'a T, analyze: bool) -> Result<Explanation>
+where
+    T: AsTable + ApplyTableFilterByValue<'a>,
+{
+    let plan = LogicalPlan::build(query, table)?;
+    let optimized = optimizer::optimize(plan.clone(), table);
+    let access_path = optimized
+        .scan_filter()
+        .map(|scan_filter| table.access_path(&scan_filter));
+
+    let analysis = if analyze {
+        let baseline = memory::reset_peak();
+        let start = Instant::now();
+
+        let (rows, stats) = optimized.execute_analyzed(table);
+        let row_count = rows.count();
+
+        if cancel::is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        Some(Analysis {
+            total_time: start.elapsed(),
+            peak_memory: memory::peak().saturating_sub(baseline),
+            stats: stats.iter().map(|stats| stats.borrow().clone()).collect(),
+            row_count,
+        })
+    } else {
+        None
+    };
+
+    Ok(Explanation {
+        query: query.clone(),
+        plan,
+        optimized,
+        access_path,
+        analysis,
+    })
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Query: {}\n", self.query)?;
+        writeln!(f, "Plan:\n{}\n", self.plan)?;
+        writeln!(f, "Optimized plan:")?;
+
+        match &self.analysis {
+            Some(analysis) => analysis.fmt_plan(f, &self.optimized)?,
+            None => writeln!(f, "{}", self.optimized)?,
+        }
+
+        match &self.access_path {
+            Some(access_path) => write!(f, "\nAccess path: {access_path}")?,
+            None => write!(f, "\nAccess path: none, the table is not read")?,
+        }
+
+        if let Some(analysis) = &self.analysis {
+            write!(
+                f,
+                "\nReturned {} rows in {}, peak memory: {}",
+                analysis.row_count,
+                format_duration(analysis.total_time),
+                memory::format_bytes(analysis.peak_memory)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Analysis {
+    /// Writes the plan tree with the statistics of every node.
+    fn fmt_plan(&self, f: &mut std::fmt::Formatter<'_>, plan: &LogicalPlan) -> std::fmt::Result {
+        for (depth, (node, stats)) in plan.nodes().zip(&self.stats).enumerate() {
+            // The time of a node includes the time of its input.
+            let input_time = self
+                .stats
+                .get(depth + 1)
+                .map_or(Duration::ZERO, |input_stats| input_stats.elapsed);
+            let time = stats.elapsed.saturating_sub(input_time);
+
+            write!(
+                f,
+                "{:indent$}{} (rows: {}",
+                "",
+                node.describe(),
+                stats.rows,
+                indent = depth * 2
+            )?;
+            if let Some(rows_scanned) = stats.rows_scanned {
+                write!(f, ", scanned: {rows_scanned}")?;
+            }
+            writeln!(f, ", time: {})", format_duration(time))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the duration in milliseconds.
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
@@ -7,6 +7,8 @@ use pest_derive::Parser;
 
 use crate::{
     error::{FilterError, Result},
+    index::AccessPath,
+    output::OutputMode,
     table::{ColumnValue, IntegerColumnType},
 };
 
@@ -60,13 +62,132 @@ pub struct FilterByValue {
     pub value: ColumnValue,
 }
 
+/// The order of the sorted rows.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    /// From the smallest value to the largest one.
+    #[default]
+    Ascending,
+    /// From the largest value to the smallest one.
+    Descending,
+}
+
+impl SortOrder {
+    /// Returns the string representation of the sort order.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "ASC",
+            SortOrder::Descending => "DESC",
+        }
+    }
+}
+
+impl std::fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Represents the ordering of the rows by a column.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrderBy {
+    /// The column to sort the rows by.
+    pub column: String,
+    /// The order of the rows.
+    pub order: SortOrder,
+}
+
 /// Represents the filter for one or more columns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FilterColumns {
     /// The columns to return (the projection).
     pub output_columns: Vec<String>,
+    /// The table to query, the default one if [`None`].
+    pub table: Option<String>,
     /// The values to compare against. A map of column names to filters.
     pub filters: HashMap<String, FilterByValue>,
+    /// The ordering of the returned rows, if any.
+    pub order_by: Option<OrderBy>,
+    /// The maximum number of the returned rows, if any.
+    pub limit: Option<usize>,
+    /// The file to write the rows to instead of printing them, if any.
+    pub into: Option<String>,
+    /// The format to output the rows in, the one of the session if
+    /// [`None`].
+    pub format: Option<OutputMode>,
+}
+
+impl FilterColumns {
+    /// Returns the columns of the table the query refers to, in the order
+    /// of the table, or an error naming the first missing one.
+    pub fn referenced_columns<'a>(&self, columns: &'a [String]) -> Result<Vec<&'a String>> {
+        let referenced = self
+            .output_columns
+            .iter()
+            .chain(self.filters.keys())
+            .chain(self.order_by.iter().map(|order_by| &order_by.column));
+
+        if let Some(column) = referenced.clone().find(|column| !columns.contains(column)) {
+            return Err(crate::error::Error::ColumnNotFound(column.clone()));
+        }
+
+        Ok(columns
+            .iter()
+            .filter(|column| referenced.clone().any(|referenced| referenced == *column))
+            .collect())
+    }
+}
+
+impl std::fmt::Display for FilterColumns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut filters: Vec<_> = self.filters.iter().collect();
+        filters.sort_by_key(|(column, _)| *column);
+
+        write!(f, "PROJECT {}", self.output_columns.join(", "))?;
+
+        if let Some(table) = &self.table {
+            write!(f, " FROM {table}")?;
+        }
+
+        write!(f, " FILTER ")?;
+
+        for (i, (column, filter)) in filters.into_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{column} {} {}", filter.operation, filter.value)?;
+        }
+
+        if let Some(order_by) = &self.order_by {
+            write!(f, " ORDER BY {} {}", order_by.column, order_by.order)?;
+        }
+
+        if let Some(limit) = self.limit {
+            write!(f, " LIMIT {limit}")?;
+        }
+
+        if let Some(into) = &self.into {
+            write!(f, " INTO '{into}'")?;
+        }
+
+        if let Some(format) = self.format {
+            write!(f, " FORMAT {format}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns [`true`] if the row satisfies all the filters. A filter on a
+/// column missing from the row is never satisfied.
+pub fn row_matches(
+    row: &HashMap<String, &ColumnValue>,
+    filters: &HashMap<String, FilterByValue>,
+) -> bool {
+    filters.iter().all(|(name, filter)| {
+        row.get(name)
+            .is_some_and(|value| value.apply_filter_by_value(filter).unwrap_or(false))
+    })
 }
 
 impl TryFrom<&str> for FilterColumns {
@@ -107,12 +228,19 @@ pub trait ApplyTableFilterByValue<'a> {
     /// The first element of the tuple is the name of the column, and
     /// the second element is the column values.
     fn apply_filter(&'a self, filter: &FilterColumns) -> FilterQueryIterator<'a>;
+
+    /// Returns the way the rows are accessed when applying the filter.
+    fn access_path(&self, _filter: &FilterColumns) -> AccessPath {
+        AccessPath::Full
+    }
 }
 
 /// A filter query iterator.
 pub struct FilterQueryIterator<'a> {
     data: Box<dyn Iterator<Item = HashMap<String, &'a ColumnValue>> + 'a>,
     filter: FilterColumns,
+    /// The number of rows taken from the data so far.
+    rows_scanned: usize,
 }
 
 impl<'a> FilterQueryIterator<'a> {
@@ -121,7 +249,16 @@ impl<'a> FilterQueryIterator<'a> {
         data: Box<dyn Iterator<Item = HashMap<String, &'a ColumnValue>> + 'a>,
         filter: FilterColumns,
     ) -> Self {
-        Self { data, filter }
+        Self {
+            data,
+            filter,
+            rows_scanned: 0,
+        }
+    }
+
+    /// Returns the number of rows the filter was applied to so far.
+    pub fn rows_scanned(&self) -> usize {
+        self.rows_scanned
     }
 }
 
@@ -134,23 +271,19 @@ impl<'a> Iterator for FilterQueryIterator<'a> {
         }
 
         for row in self.data.by_ref() {
-            let mut filtered_row = HashMap::new();
-            let mut should_return = false;
-
-            for (name, value) in row {
-                let filter = self.filter.filters.get(&name);
-
-                if let Some(filter) = filter {
-                    should_return = value.apply_filter_by_value(filter).unwrap_or(false);
-                }
-
-                if self.filter.output_columns.contains(&name.to_string()) {
-                    filtered_row.insert(name.clone(), value);
-                }
+            if crate::cancel::is_cancelled() {
+                return None;
             }
 
-            if should_return {
-                return Some(filtered_row);
+            self.rows_scanned += 1;
+
+            // All the filters must match for the row to be returned.
+            if row_matches(&row, &self.filter.filters) {
+                return Some(
+                    row.into_iter()
+                        .filter(|(name, _)| self.filter.output_columns.contains(name))
+                        .collect(),
+                );
             }
         }
 
@@ -160,37 +293,132 @@ impl<'a> Iterator for FilterQueryIterator<'a> {
 
 #[derive(Parser)]
 #[grammar_inline = r#"
-// Main rules
-query   = { project ~ filters }
+// Main rules, which take the whole input
+statement    = { SOI ~ (explain | create_index | query) ~ EOI }
+filter_query = { SOI ~ query ~ EOI }
+explain      = { "EXPLAIN" ~ analyze? ~ query }
+analyze      = { "ANALYZE" }
+create_index = { "CREATE" ~ "INDEX" ~ "ON" ~ column ~ from? }
+query   = { project ~ from? ~ filters ~ order_by? ~ limit? ~ into? ~ format? }
 project = { "PROJECT" ~ columns }
+from     = { "FROM" ~ table }
 filters  = { "FILTER" ~ filter }
 filter = { filter_expression ~ ("," ~ filter_expression)* }
 filter_expression = { column ~ op ~ value }
+order_by = { "ORDER" ~ "BY" ~ column ~ sort_order? }
+limit    = { "LIMIT" ~ count }
+into     = { "INTO" ~ path }
+format   = { "FORMAT" ~ format_name }
 
 // Main tokens
 columns = { column ~ ("," ~ column)* }
-column  = @{ ASCII_ALPHANUMERIC+ }
+column  = @{ (ASCII_ALPHANUMERIC | "_")+ }
+table   = @{ (ASCII_ALPHANUMERIC | "_")+ }
 op      = @{ "<" | "=" | ">" }
-value   = { ASCII_DIGIT+ | "\"" ~ ASCII_ALPHANUMERIC* ~ "\"" }
+value   = { integer | "\"" ~ ASCII_ALPHANUMERIC* ~ "\"" }
+integer = @{ ASCII_DIGIT+ }
+sort_order = @{ "ASC" | "DESC" }
+count   = @{ ASCII_DIGIT+ }
+format_name = @{ ASCII_ALPHA+ }
+path    = @{ "'" ~ (!"'" ~ ANY)+ ~ "'" }
 
 // Basic rules
-WHITESPACE = _{ " "+ }
+WHITESPACE = _{ " " | "\t" | NEWLINE }
 "#]
 struct QueryParser;
 
+/// A single statement which can be executed by the engine.
+#[derive(Debug, Clone)]
+pub enum Statement {
+    /// Run the query and output the results.
+    Query(FilterColumns),
+    /// Describe how the query would be executed without running it.
+    Explain(FilterColumns),
+    /// Run the query and describe how it was executed.
+    ExplainAnalyze(FilterColumns),
+    /// Build an index on the column.
+    CreateIndex {
+        /// The table to build the index for, the default one if [`None`].
+        table: Option<String>,
+        /// The column to index.
+        column: String,
+    },
+}
+
+/// Parses a statement string into a [`Statement`].
+pub fn parse_statement(input: &str) -> Result<Statement> {
+    let mut pairs = QueryParser::parse(Rule::statement, input)
+        .map_err(|e| FilterError::Parse(e.to_string()))?;
+
+    let pair = pairs
+        .next()
+        .and_then(|statement| statement.into_inner().next())
+        .ok_or_else(|| FilterError::Parse("Expected statement".to_string()))?;
+
+    Ok(match pair.as_rule() {
+        Rule::query => Statement::Query(parse_query_pair(pair)?),
+        Rule::explain => {
+            let mut inner_rules = pair.into_inner().peekable();
+            let analyze = inner_rules
+                .next_if(|pair| pair.as_rule() == Rule::analyze)
+                .is_some();
+            let query_pair = inner_rules
+                .next()
+                .ok_or_else(|| FilterError::Parse("Expected query".to_string()))?;
+            let query = parse_query_pair(query_pair)?;
+
+            if analyze {
+                Statement::ExplainAnalyze(query)
+            } else {
+                Statement::Explain(query)
+            }
+        }
+        Rule::create_index => {
+            let mut inner_rules = pair.into_inner();
+            let column = inner_rules
+                .next()
+                .ok_or_else(|| FilterError::Parse("Expected column".to_string()))?;
+            let table = inner_rules.next().map(parse_from_pair);
+
+            Statement::CreateIndex {
+                table,
+                column: column.as_str().to_string(),
+            }
+        }
+        _ => return Err(FilterError::Parse("Expected statement".to_string()).into()),
+    })
+}
+
 /// Parses a filter query string into a [`FilterColumns`] struct.
 pub fn parse_filter_query(input: &str) -> Result<FilterColumns> {
-    let mut pairs =
-        QueryParser::parse(Rule::query, input).map_err(|e| FilterError::Parse(e.to_string()))?;
-
-    let mut output_columns = Vec::new();
-    let mut filters = HashMap::new();
+    let mut pairs = QueryParser::parse(Rule::filter_query, input)
+        .map_err(|e| FilterError::Parse(e.to_string()))?;
 
-    // There should be a single pair representing the entire query
+    // There should be a single pair representing the entire query,
+    // followed by the end of the input
     let query_pair = pairs
         .next()
+        .and_then(|filter_query| filter_query.into_inner().next())
         .ok_or_else(|| FilterError::Parse("Expected query".to_string()))?;
 
+    parse_query_pair(query_pair)
+}
+
+/// Returns the table name of a parsed `from` rule.
+fn parse_from_pair(from_pair: pest::iterators::Pair<'_, Rule>) -> String {
+    from_pair.into_inner().as_str().to_string()
+}
+
+/// Converts a parsed `query` rule into a [`FilterColumns`] struct.
+fn parse_query_pair(query_pair: pest::iterators::Pair<'_, Rule>) -> Result<FilterColumns> {
+    let mut output_columns = Vec::new();
+    let mut table = None;
+    let mut filters = HashMap::new();
+    let mut order_by = None;
+    let mut limit = None;
+    let mut into = None;
+    let mut format = None;
+
     // Iterate over the inner pairs of the `query` rule
     for pair in query_pair.into_inner() {
         match pair.as_rule() {
@@ -207,6 +435,7 @@ pub fn parse_filter_query(input: &str) -> Result<FilterColumns> {
                     }
                 }
             }
+            Rule::from => table = Some(parse_from_pair(pair)),
             Rule::filters => {
                 for filter in pair.into_inner() {
                     if filter.as_rule() != Rule::filter {
@@ -244,6 +473,37 @@ pub fn parse_filter_query(input: &str) -> Result<FilterColumns> {
                     }
                 }
             }
+            Rule::order_by => {
+                let mut inner_rules = pair.into_inner();
+
+                let column = inner_rules.next().unwrap().as_str().to_string();
+                let order = match inner_rules.next().map(|order| order.as_str()) {
+                    Some("DESC") => SortOrder::Descending,
+                    _ => SortOrder::Ascending,
+                };
+
+                order_by = Some(OrderBy { column, order });
+            }
+            Rule::limit => {
+                let count = pair.into_inner().next().unwrap().as_str();
+
+                limit = Some(
+                    count
+                        .parse()
+                        .map_err(|_| FilterError::Parse(format!("Invalid limit: {count}")))?,
+                );
+            }
+            Rule::into => {
+                into = Some(pair.into_inner().as_str().trim_matches('\'').to_string());
+            }
+            Rule::format => {
+                let name = pair.into_inner().as_str();
+
+                format = Some(
+                    name.parse()
+                        .map_err(|_| FilterError::Parse(format!("Unknown format: {name}")))?,
+                );
+            }
             _ => {}
         }
     }
@@ -251,7 +511,12 @@ pub fn parse_filter_query(input: &str) -> Result<FilterColumns> {
     // Return the parsed FilterColumns
     Ok(FilterColumns {
         output_columns,
+        table,
         filters,
+        order_by,
+        limit,
+        into,
+        format,
     })
 }
 
@@ -285,4 +550,105 @@ mod tests {
             ColumnValue::String(StringColumnType("value".to_string()))
         );
     }
+
+    #[test]
+    fn parse_statement_succeeds() {
+        let statement = parse_statement("CREATE INDEX ON col1").unwrap();
+        assert!(matches!(
+            statement,
+            Statement::CreateIndex { table: None, column } if column == "col1"
+        ));
+
+        let statement = parse_statement("CREATE INDEX ON col1 FROM other_table").unwrap();
+        assert!(matches!(
+            statement,
+            Statement::CreateIndex { table: Some(table), column }
+                if table == "other_table" && column == "col1"
+        ));
+
+        let statement = parse_statement("EXPLAIN PROJECT col1 FILTER col2 > 5").unwrap();
+        let Statement::Explain(filter) = statement else {
+            panic!("Expected an EXPLAIN statement");
+        };
+        assert_eq!(filter.output_columns, vec!["col1".to_string()]);
+        assert_eq!(
+            filter.filters.get("col2").unwrap().operation,
+            Operation::GreaterThan
+        );
+
+        let statement = parse_statement("EXPLAIN ANALYZE PROJECT col1 FILTER col2 > 5").unwrap();
+        assert!(matches!(statement, Statement::ExplainAnalyze(_)));
+
+        let statement = parse_statement("PROJECT col1 FILTER col2 = 1").unwrap();
+        assert!(matches!(statement, Statement::Query(_)));
+
+        let statement = parse_statement("PROJECT col1\n  FILTER col2 = 1").unwrap();
+        assert!(matches!(statement, Statement::Query(_)));
+
+        let statement = parse_statement("PROJECT col1 FROM other_table FILTER col2 = 1").unwrap();
+        let Statement::Query(filter) = statement else {
+            panic!("Expected a query");
+        };
+        assert_eq!(filter.table.as_deref(), Some("other_table"));
+        assert_eq!(
+            filter.to_string(),
+            "PROJECT col1 FROM other_table FILTER col2 = 1"
+        );
+
+        assert!(parse_statement("PROJECT col1 FILTER col2 > 1 LIMT 5").is_err());
+        assert!(parse_statement("CREATE INDEX ON col1 col2").is_err());
+        assert!(parse_filter_query("PROJECT col1 FILTER col2 > 1 LIMT 5").is_err());
+    }
+
+    #[test]
+    fn parse_order_by_and_limit_succeeds() {
+        let filter =
+            parse_filter_query("PROJECT col1 FILTER col2 > 1 ORDER BY col1 DESC LIMIT 2").unwrap();
+
+        assert_eq!(
+            filter.order_by,
+            Some(OrderBy {
+                column: "col1".to_string(),
+                order: SortOrder::Descending,
+            })
+        );
+        assert_eq!(filter.limit, Some(2));
+        assert_eq!(
+            filter.filters.get("col2").unwrap().value,
+            ColumnValue::Integer(IntegerColumnType(1))
+        );
+
+        let filter = parse_filter_query("PROJECT col1 FILTER col2 > 1 ORDER BY col1").unwrap();
+        assert_eq!(filter.order_by.unwrap().order, SortOrder::Ascending);
+        assert_eq!(filter.limit, None);
+        assert_eq!(filter.format, None);
+
+        let filter =
+            parse_filter_query("PROJECT col1 FILTER col2 > 1 LIMIT 2 FORMAT json").unwrap();
+        assert_eq!(filter.format, Some(OutputMode::Json));
+
+        let filter =
+            parse_filter_query("PROJECT col1 FILTER col2 > 1 INTO 'out dir/out.csv'").unwrap();
+        assert_eq!(filter.into.as_deref(), Some("out dir/out.csv"));
+        assert_eq!(
+            filter.to_string(),
+            "PROJECT col1 FILTER col2 > 1 INTO 'out dir/out.csv'"
+        );
+        assert!(parse_filter_query("PROJECT col1 FILTER col2 > 1 FORMAT xml").is_err());
+    }
+
+    #[test]
+    fn referenced_columns() {
+        let columns = ["a", "b", "c", "d"].map(String::from);
+        let filter = parse_filter_query("PROJECT c, a FILTER b > 1 ORDER BY c").unwrap();
+
+        assert_eq!(
+            filter.referenced_columns(&columns).unwrap(),
+            vec!["a", "b", "c"]
+        );
+        assert!(matches!(
+            filter.referenced_columns(&columns[1..]),
+            Err(crate::error::Error::ColumnNotFound(column)) if column == "a"
+        ));
+    }
 }
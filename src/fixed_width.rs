@@ -0,0 +1,242 @@
+//! The layouts of the fixed-width files.
+
+use std::{
+    io::{BufRead, BufReader, Read},
+    str::FromStr,
+};
+
+use crate::{
+    error::{Error, Result},
+    table::{ColumnType, ColumnValue},
+};
+
+/// A column of a fixed-width file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedWidthColumn {
+    /// The name of the column.
+    pub name: String,
+    /// The position of the first character of the column in a line,
+    /// starting from 1.
+    pub start: usize,
+    /// The number of characters in the column.
+    pub width: usize,
+    /// The type of the values.
+    pub column_type: ColumnType,
+}
+
+/// The layout of a fixed-width file: its columns, in the order they are
+/// loaded in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedWidthLayout {
+    columns: Vec<FixedWidthColumn>,
+}
+
+impl FixedWidthLayout {
+    /// Creates the layout of the columns, which must have distinct names
+    /// and span at least a character each.
+    pub fn new(columns: Vec<FixedWidthColumn>) -> Result<Self> {
+        if columns.is_empty() {
+            return Err(Error::ValueParse("The layout has no columns".to_owned()));
+        }
+
+        for (i, column) in columns.iter().enumerate() {
+            if column.start == 0 || column.width == 0 {
+                return Err(Error::ValueParse(format!(
+                    "The column {} must start at 1 or later and be at least 1 character wide",
+                    column.name
+                )));
+            }
+            if columns[..i].iter().any(|other| other.name == column.name) {
+                return Err(Error::ValueParse(format!(
+                    "The column {} is defined twice",
+                    column.name
+                )));
+            }
+        }
+
+        Ok(Self { columns })
+    }
+
+    /// Reads the layout from the file, in the format of [`FromStr`].
+    pub fn from_file(path: &str) -> Result<Self> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Splits the line into the values of the columns.
+    ///
+    /// The whitespace around the values is removed, and the empty values
+    /// as well as the ones past the end of the line are nulls. The values
+    /// of the integer columns must be integers otherwise.
+    pub fn split(&self, line: &str) -> Result<Vec<ColumnValue>> {
+        let chars: Vec<char> = line.chars().collect();
+
+        self.columns
+            .iter()
+            .map(|column| {
+                let start = (column.start - 1).min(chars.len());
+                let end = (start + column.width).min(chars.len());
+                let value: String = chars[start..end].iter().collect();
+                let value = value.trim();
+
+                match column.column_type {
+                    _ if value.is_empty() => Ok(ColumnValue::null()),
+                    ColumnType::Integer => value.parse().map(ColumnValue::Integer).map_err(|_| {
+                        Error::ValueParse(format!(
+                            "{value} in the column {} is not an integer",
+                            column.name
+                        ))
+                    }),
+                    ColumnType::String => Ok(ColumnValue::String(value.into())),
+                }
+            })
+            .collect()
+    }
+
+    /// Reads the fixed-width lines from the reader into the named columns,
+    /// skipping the blank lines.
+    pub fn read(&self, reader: impl Read) -> Result<Vec<(String, Vec<ColumnValue>)>> {
+        let mut values: Vec<Vec<ColumnValue>> = vec![Vec::new(); self.columns.len()];
+
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            for (values, value) in values.iter_mut().zip(self.split(&line)?) {
+                values.push(value);
+            }
+        }
+
+        let names = self.columns.iter().map(|column| column.name.clone());
+
+        Ok(names.zip(values).collect())
+    }
+}
+
+/// Parses the layout, a column per line as `NAME START WIDTH TYPE`, the
+/// type being `integer` or `string`. The blank lines and the ones starting
+/// with `#` are skipped.
+impl FromStr for FixedWidthLayout {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let columns = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let invalid = || {
+                    Error::ValueParse(format!(
+                        "Expected NAME START WIDTH TYPE in the layout, got {line}"
+                    ))
+                };
+
+                match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    [name, start, width, column_type] => Ok(FixedWidthColumn {
+                        name: name.to_string(),
+                        start: start.parse().map_err(|_| invalid())?,
+                        width: width.parse().map_err(|_| invalid())?,
+                        column_type: column_type.parse()?,
+                    }),
+                    _ => Err(invalid()),
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        Self::new(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        csv_table::CsvTable,
+        table::{AsTable, IntegerColumnType},
+    };
+
+    use super::*;
+
+    #[test]
+    fn parses_layout() {
+        let layout: FixedWidthLayout = "# name start width type\n\
+                                        id    1  4 integer\n\
+                                        \n\
+                                        name  5 10 String\n"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            layout.columns,
+            [
+                FixedWidthColumn {
+                    name: "id".to_string(),
+                    start: 1,
+                    width: 4,
+                    column_type: ColumnType::Integer,
+                },
+                FixedWidthColumn {
+                    name: "name".to_string(),
+                    start: 5,
+                    width: 10,
+                    column_type: ColumnType::String,
+                },
+            ]
+        );
+
+        assert!("id 1 4".parse::<FixedWidthLayout>().is_err());
+        assert!("id 0 4 integer".parse::<FixedWidthLayout>().is_err());
+        assert!("id 1 4 float".parse::<FixedWidthLayout>().is_err());
+        assert!("id 1 4 integer\nid 5 1 string"
+            .parse::<FixedWidthLayout>()
+            .is_err());
+    }
+
+    #[test]
+    fn splits_lines() {
+        let layout: FixedWidthLayout = "id 1 3 integer\nname 4 6 string\ncode 10 2 string"
+            .parse()
+            .unwrap();
+        let string = |value: &str| ColumnValue::String(value.into());
+
+        assert_eq!(
+            layout.split(" 42Zoë   FR").unwrap(),
+            vec![
+                ColumnValue::Integer(IntegerColumnType(42)),
+                string("Zoë"),
+                string("FR"),
+            ]
+        );
+        assert_eq!(
+            layout.split("   Ann").unwrap(),
+            vec![string(""), string("Ann"), string("")]
+        );
+        assert!(layout.split("abcAnn").is_err());
+    }
+
+    #[test]
+    fn reads_lines() {
+        let layout: FixedWidthLayout = "id 1 3 integer\nname 4 8 string\nqty 12 4 integer"
+            .parse()
+            .unwrap();
+        let data = b"  1widget     10\r\n\n  2gadget        \n 10gizmo       7\n";
+        let table = CsvTable::from_columns(layout.read(data.as_slice()).unwrap());
+
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(
+            table.get_column_names().collect::<Vec<_>>(),
+            vec!["id", "name", "qty"]
+        );
+        assert_eq!(table.get_column_type("id"), Some(ColumnType::Integer));
+        assert_eq!(table.get_column_type("qty"), None);
+
+        let result = table.execute("PROJECT name FILTER id > 1").unwrap();
+        assert_eq!(
+            result.into_rows(),
+            vec![
+                vec![ColumnValue::String("gadget".into())],
+                vec![ColumnValue::String("gizmo".into())],
+            ]
+        );
+    }
+}
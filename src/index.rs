@@ -0,0 +1,137 @@
+//! Secondary indexes over the table columns.
+
+use std::collections::BTreeMap;
+use std::ops::{Bound, Range};
+
+use crate::{
+    cancel,
+    error::{Error, Result},
+    filter::{FilterByValue, Operation},
+    table::ColumnValue,
+};
+
+/// A sorted secondary index over a single column.
+///
+/// Maps every distinct value of the column to the row numbers holding
+/// it. Being ordered, the same index serves both the equality and the
+/// range lookups.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnIndex {
+    entries: BTreeMap<ColumnValue, Vec<usize>>,
+}
+
+impl ColumnIndex {
+    /// Builds the index from the column values, unless the statement is
+    /// cancelled meanwhile.
+    pub fn build<'a>(values: impl IntoIterator<Item = &'a ColumnValue>) -> Result<Self> {
+        let mut entries: BTreeMap<ColumnValue, Vec<usize>> = BTreeMap::new();
+
+        for (row, value) in values.into_iter().enumerate() {
+            if cancel::is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+
+            entries.entry(value.clone()).or_default().push(row);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the sorted row numbers whose value satisfies the filter.
+    ///
+    /// Values of a different type than the filter value never match,
+    /// the same way the filter itself treats them during a full scan.
+    pub fn lookup(&self, filter: &FilterByValue) -> Vec<usize> {
+        let value = &filter.value;
+        let same_type = |(key, _): &(&ColumnValue, &Vec<usize>)| key.get_type() == value.get_type();
+
+        let mut rows: Vec<usize> = match filter.operation {
+            Operation::Equal => self.entries.get(value).cloned().unwrap_or_default(),
+            Operation::GreaterThan => self
+                .entries
+                .range((Bound::Excluded(value), Bound::Unbounded))
+                .take_while(same_type)
+                .flat_map(|(_, rows)| rows.iter().copied())
+                .collect(),
+            Operation::LessThan => self
+                .entries
+                .range(..value)
+                .rev()
+                .take_while(same_type)
+                .flat_map(|(_, rows)| rows.iter().copied())
+                .collect(),
+        };
+
+        rows.sort_unstable();
+        rows
+    }
+}
+
+/// The way the rows of a table are accessed when executing a query.
+#[derive(Debug, Clone)]
+pub enum AccessPath {
+    /// Every row of the table is visited.
+    Full,
+    /// Only the blocks of rows whose zone maps may satisfy the filters
+    /// are visited.
+    Pruned {
+        /// The rows of the blocks to visit.
+        rows: Vec<Range<usize>>,
+        /// The number of the skipped blocks.
+        skipped_blocks: usize,
+        /// The total number of blocks.
+        total_blocks: usize,
+    },
+    /// Only the rows returned by the index lookup are visited.
+    Index {
+        /// The indexed column.
+        column: String,
+        /// The filter used for the lookup.
+        filter: FilterByValue,
+    },
+    /// Only the rows whose dictionary code equals the code of the
+    /// filter value are visited.
+    Dictionary {
+        /// The dictionary-encoded column.
+        column: String,
+        /// The equality filter.
+        filter: FilterByValue,
+    },
+}
+
+impl AccessPath {
+    /// Returns the column whose filter is fully satisfied by the access
+    /// path, so it doesn't have to be evaluated again.
+    pub fn satisfied_column(&self) -> Option<&str> {
+        match self {
+            Self::Full | Self::Pruned { .. } => None,
+            Self::Index { column, .. } | Self::Dictionary { column, .. } => Some(column),
+        }
+    }
+}
+
+impl std::fmt::Display for AccessPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "Full scan"),
+            Self::Pruned {
+                skipped_blocks,
+                total_blocks,
+                ..
+            } => write!(
+                f,
+                "Full scan skipping {skipped_blocks} of {total_blocks} blocks by zone maps"
+            ),
+            Self::Index { column, filter } => write!(
+                f,
+                "Index scan on {column} ({column} {} {})",
+                filter.operation, filter.value
+            ),
+            Self::Dictionary { column, filter } => write!(
+                f,
+                "Dictionary scan on {column} ({column} {} {})",
+                filter.operation, filter.value
+            ),
+        }
+    }
+}
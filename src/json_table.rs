@@ -0,0 +1,206 @@
+//! The tables loaded from the JSON and newline-delimited JSON files.
+
+use std::{error::Error, io::Read};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    compression,
+    csv_table::CsvTable,
+    error::Error as CrateError,
+    table::{self, ColumnValue, IntegerColumnType, StringColumnType},
+};
+
+/// The JSON objects loaded into memory, whose top-level keys are the
+/// columns.
+#[derive(Debug)]
+pub struct JsonTable {
+    /// The values of the objects.
+    pub table: CsvTable,
+    /// Whether the objects were read one per line rather than from an
+    /// array.
+    pub ndjson: bool,
+}
+
+impl JsonTable {
+    /// Loads the JSON file into memory, decompressing it if it is
+    /// compressed with gzip, zstd or bzip2.
+    pub fn from_json(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        Self::from_reader(compression::open(file_path)?)
+    }
+
+    /// Loads the JSON data read from the reader into memory: either an
+    /// array of objects or the objects one after another, usually one
+    /// per line.
+    ///
+    /// The keys become the column names, with the characters not allowed
+    /// in the statements replaced by `_`. The values missing from an
+    /// object are empty, as are the nulls.
+    /// The integers are kept as such, while the other numbers, booleans,
+    /// arrays and objects become their JSON text.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Box<dyn Error>> {
+        let mut objects = Vec::new();
+        let mut ndjson = true;
+
+        for (i, value) in serde_json::Deserializer::from_reader(reader)
+            .into_iter::<Value>()
+            .enumerate()
+        {
+            match value? {
+                Value::Array(values) if i == 0 => {
+                    ndjson = false;
+                    for value in values {
+                        objects.push(into_object(value)?);
+                    }
+                }
+                value => objects.push(into_object(value)?),
+            }
+        }
+
+        // Every object is a row, even the empty ones after the last key.
+        let row_count = objects.len();
+        let mut columns: Vec<(String, Vec<ColumnValue>)> = Vec::new();
+        for (row_idx, object) in objects.into_iter().enumerate() {
+            for (key, value) in object {
+                match columns.iter_mut().find(|(name, _)| *name == key) {
+                    Some((_, values)) => {
+                        values.resize(row_idx, ColumnValue::null());
+                        values.push(to_column_value(value));
+                    }
+                    None => {
+                        let mut values = vec![ColumnValue::null(); row_idx];
+                        values.push(to_column_value(value));
+                        columns.push((key, values));
+                    }
+                }
+            }
+        }
+
+        for (_, values) in &mut columns {
+            values.resize(row_count, ColumnValue::null());
+        }
+
+        let columns = columns
+            .into_iter()
+            .map(|(key, values)| (table::identifier(&key), values));
+
+        Ok(Self {
+            table: CsvTable::from_columns(columns),
+            ndjson,
+        })
+    }
+
+    /// Returns the name of the format the objects were read in.
+    pub fn format(&self) -> &'static str {
+        if self.ndjson {
+            "NDJSON"
+        } else {
+            "JSON"
+        }
+    }
+}
+
+/// Returns the value as an object, or an error if it is not one.
+fn into_object(value: Value) -> Result<Map<String, Value>, CrateError> {
+    match value {
+        Value::Object(object) => Ok(object),
+        value => Err(CrateError::ValueParse(format!(
+            "Expected a JSON object, got {value}"
+        ))),
+    }
+}
+
+/// Converts the JSON value into a column value.
+fn to_column_value(value: Value) -> ColumnValue {
+    match value {
+        Value::Null => ColumnValue::null(),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => ColumnValue::Integer(IntegerColumnType(integer)),
+            None => ColumnValue::String(StringColumnType(number.to_string())),
+        },
+        Value::String(string) => ColumnValue::String(StringColumnType(string)),
+        value => ColumnValue::String(StringColumnType(value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        filter::FilterColumns,
+        result_set::ResultSet,
+        table::{AsTable, ColumnType},
+    };
+
+    #[test]
+    fn load_ndjson() {
+        let data = br#"{"id": 1, "level": "info", "ok": true}
+{"id": 2, "level": "error", "tags": ["a"], "ratio": 0.5}
+{"level": "info", "id": 3, "ok": null}
+"#;
+        let JsonTable { table, ndjson } = JsonTable::from_reader(data.as_slice()).unwrap();
+
+        assert!(ndjson);
+        assert_eq!(
+            table.get_column_names().collect::<Vec<_>>(),
+            vec!["id", "level", "ok", "tags", "ratio"]
+        );
+        assert_eq!(table.get_column_type("id"), Some(ColumnType::Integer));
+        assert_eq!(table.get_column_type("level"), Some(ColumnType::String));
+
+        let filter = FilterColumns::try_from(r#"PROJECT id, ok, tags FILTER level = "info""#);
+        let result = ResultSet::execute(&filter.unwrap(), &table).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[0],
+            [
+                ColumnValue::Integer(IntegerColumnType(1)),
+                ColumnValue::String(StringColumnType("true".to_string())),
+                ColumnValue::null(),
+            ]
+        );
+        assert_eq!(
+            result.value(1, "id"),
+            Some(&ColumnValue::Integer(IntegerColumnType(3)))
+        );
+    }
+
+    #[test]
+    fn load_array() {
+        let JsonTable { table, ndjson } =
+            JsonTable::from_reader(br#"[{"a": 1}, {"b": "x"}]"#.as_slice()).unwrap();
+
+        assert!(!ndjson);
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(
+            table.get_columns().nth(1).unwrap().1.get(0),
+            Some(&ColumnValue::null())
+        );
+
+        assert!(JsonTable::from_reader(b"[1, 2]".as_slice()).is_err());
+    }
+
+    #[test]
+    fn names_columns() {
+        let data = br#"{"id": 1, "user-agent": "curl", "user agent": "wget"}"#;
+        let table = JsonTable::from_reader(data.as_slice()).unwrap().table;
+
+        assert_eq!(
+            table.get_column_names().collect::<Vec<_>>(),
+            vec!["id", "user_agent", "user_agent_2"]
+        );
+    }
+
+    #[test]
+    fn keeps_empty_objects() {
+        let table = JsonTable::from_reader(b"{\"a\": 1}\n{}\n{}\n".as_slice())
+            .unwrap()
+            .table;
+
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(
+            table.get_columns().next().unwrap().1.get(2),
+            Some(&ColumnValue::null())
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Queries the CSV, JSON, Parquet, Arrow, SQLite, fixed-width and
+//! spreadsheet files with the `PROJECT ... FILTER ...` statements.
+//!
+//! A [`csv_table::CsvTable`] is queried with [`result_set::ResultSet::execute`],
+//! the result being written with the [`output`] writers or exchanged as an
+//! Arrow `RecordBatch` through the conversions of [`record_batch`].
+//!
+//! ```no_run
+//! use prisma_test::{
+//!     csv_table::CsvTable,
+//!     filter::FilterColumns,
+//!     output::{JsonWriter, ResultWriter},
+//!     result_set::ResultSet,
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let table = CsvTable::from_csv("data.csv")?;
+//! let filter = FilterColumns::try_from("PROJECT col1 FILTER col3 > 5")?;
+//! let result = ResultSet::execute(&filter, &table)?;
+//!
+//! JsonWriter.write(&mut std::io::stdout(), &result)?;
+//! let batch = result.to_record_batch()?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod cancel;
+mod column;
+pub mod completion;
+mod compression;
+pub mod csv_options;
+pub mod csv_table;
+pub mod error;
+mod explain;
+pub mod filter;
+pub mod fixed_width;
+mod index;
+pub mod json_table;
+pub mod memory;
+mod optimizer;
+pub mod output;
+pub mod parquet_table;
+mod plan;
+pub mod record_batch;
+pub mod result_set;
+pub mod session;
+mod sniffer;
+pub mod spreadsheet;
+pub mod sqlite_table;
+pub mod table;
+mod zone_map;
@@ -1,12 +1,24 @@
-use std::{error::Error, io::Write};
-
-use csv_table::CsvTable;
-use filter::FilterColumns;
-
-mod csv_table;
-mod error;
-mod filter;
-mod table;
+use std::{
+    error::Error,
+    io::{BufRead, IsTerminal},
+    process::ExitCode,
+};
+
+use clap::Parser;
+use prisma_test::{
+    csv_table,
+    filter::{self, FilterColumns},
+    fixed_width::FixedWidthLayout,
+    memory, output,
+    session::{self, LoadOptions, Session},
+};
+use repl::{is_exit_command, split_statements, statements};
+
+mod cli;
+mod repl;
+
+#[global_allocator]
+static ALLOCATOR: memory::TrackingAllocator = memory::TrackingAllocator;
 
 #[allow(dead_code)]
 fn manually() -> Result<(), Box<dyn Error>> {
@@ -15,18 +27,22 @@ fn manually() -> Result<(), Box<dyn Error>> {
     let projection = vec!["col1".to_owned(), "col2".to_owned()];
 
     data_table
-        .query(FilterColumns {
-            output_columns: projection,
-            filters: vec![(
-                "col3".to_string(),
-                filter::FilterByValue {
-                    operation: filter::Operation::GreaterThan,
-                    value: "5".parse().unwrap(),
-                },
-            )]
-            .into_iter()
-            .collect(),
-        })
+        .query(
+            FilterColumns {
+                output_columns: projection,
+                filters: vec![(
+                    "col3".to_string(),
+                    filter::FilterByValue {
+                        operation: filter::Operation::GreaterThan,
+                        value: "5".parse().unwrap(),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            },
+            output::OutputMode::default(),
+        )
         .expect("Query failed");
 
     Ok(())
@@ -40,41 +56,95 @@ fn with_parser() -> Result<(), Box<dyn Error>> {
 
     let filter = filter::parse_filter_query(query)?;
 
-    data_table.query(filter).expect("Query failed");
+    data_table
+        .query(filter, output::OutputMode::default())
+        .expect("Query failed");
 
     Ok(())
 }
 
-fn repl_loop(data_table: CsvTable) -> Result<(), Box<dyn Error>> {
-    println!("Welcome to the CSV data query tool!");
+/// Executes the statements read from the input, which end with `;` and
+/// may span multiple lines, while the meta-commands take a single line.
+/// Returns [`true`] if all of them succeeded.
+fn run_batch(mut session: Session, input: impl BufRead) -> Result<bool, Box<dyn Error>> {
+    let mut succeeded = true;
+    let mut execute = |statement: &str| {
+        if let Err(e) = session.execute(statement) {
+            eprintln!("Error occured: {e}");
+            succeeded = false;
+        }
+    };
+    let mut buffer = String::new();
+
+    for line in input.lines() {
+        let line = line?;
+        if buffer.is_empty() && is_exit_command(&line) {
+            break;
+        }
+
+        buffer.push_str(&line);
+        buffer.push('\n');
 
-    loop {
-        print!("\n (CTRL-C for exit) REPL > ");
-        let _ = std::io::stdout().flush();
+        let (statements, rest) = split_statements(&buffer);
+        statements.into_iter().for_each(&mut execute);
+        buffer = rest.trim_start().to_owned();
+    }
 
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
+    // The last statement may not end with `;`.
+    statements(&buffer).into_iter().for_each(&mut execute);
 
-        println!();
+    Ok(succeeded)
+}
 
-        let filter = match filter::parse_filter_query(&input) {
-            Ok(filter) => filter,
+fn main() -> ExitCode {
+    let args = cli::Args::parse();
+
+    if args.file == session::STDIN && args.execute.is_none() {
+        eprintln!("A query to execute is required when the file is read from the standard input");
+        return ExitCode::FAILURE;
+    }
+
+    let mut session = Session::default();
+    session.set_mode(args.format);
+    session.set_csv_options(args.csv_options());
+    session.set_sheet(args.sheet.clone());
+    let layout = match &args.layout {
+        Some(path) => match FixedWidthLayout::from_file(path) {
+            Ok(layout) => Some(layout),
             Err(e) => {
-                eprintln!("{e}");
-                continue;
+                eprintln!("Couldn't read the layout {path}: {e}");
+                return ExitCode::FAILURE;
             }
-        };
+        },
+        None => None,
+    };
+    let options = LoadOptions {
+        table: args.table.clone(),
+        layout,
+    };
+    if let Err(e) = session.load(&args.file, None, &options) {
+        eprintln!("Couldn't load {}: {e}", args.file);
+        return ExitCode::FAILURE;
+    }
 
-        if let Err(e) = data_table.query(filter) {
+    let result = if let Some(query) = args.execute {
+        statements(&query)
+            .into_iter()
+            .try_for_each(|statement| session.execute(statement))
+            .map(|()| true)
+            .map_err(Into::into)
+    } else if !std::io::stdin().is_terminal() {
+        run_batch(session, std::io::stdin().lock())
+    } else {
+        repl::run(session).map(|()| true)
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
             eprintln!("Error occured: {e}");
+            ExitCode::FAILURE
         }
     }
 }
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let data_table = csv_table::CsvTable::from_csv("data.csv")?;
-
-    repl_loop(data_table)?;
-
-    Ok(())
-}
@@ -0,0 +1,156 @@
+//! A simple rule-based optimizer of the logical query plans.
+
+use std::collections::HashMap;
+
+use crate::{filter::FilterByValue, plan::LogicalPlan, table::AsTable};
+
+/// Optimizes the plan of a query against the table.
+///
+/// The rules are applied in order:
+/// 1. The filters which can never be satisfied are folded into an empty
+///    result.
+/// 2. The filters are pushed down into the scan, so that the table can
+///    use its indexes and statistics to evaluate them.
+/// 3. The columns which are not needed by any node are not read.
+pub fn optimize(plan: LogicalPlan, table: &impl AsTable) -> LogicalPlan {
+    let plan = fold_constants(plan, table);
+    let plan = push_down_filters(plan);
+    prune_columns(plan, None)
+}
+
+/// Replaces the nodes which can never produce a row with an empty node.
+///
+/// A filter comparing a column with a value of another type is never
+/// satisfied, the same as a limit of zero rows. A node whose input is
+/// empty is empty as well.
+fn fold_constants(plan: LogicalPlan, table: &impl AsTable) -> LogicalPlan {
+    let plan = map_input(plan, |input| fold_constants(input, table));
+
+    let is_empty = match &plan {
+        LogicalPlan::Scan { filters, .. } | LogicalPlan::Filter { filters, .. } => {
+            is_never_satisfied(filters, table)
+        }
+        LogicalPlan::Limit { count, .. } => *count == 0,
+        _ => false,
+    } || matches!(plan.input(), Some(LogicalPlan::Empty { .. }));
+
+    if is_empty {
+        LogicalPlan::Empty {
+            columns: plan.output_columns().to_vec(),
+        }
+    } else {
+        plan
+    }
+}
+
+/// Returns [`true`] if any of the filters compares a column with a value
+/// of another type.
+fn is_never_satisfied(filters: &HashMap<String, FilterByValue>, table: &impl AsTable) -> bool {
+    filters.iter().any(|(column, filter)| {
+        table
+            .get_column_type(column)
+            .is_some_and(|column_type| column_type != filter.value.get_type())
+    })
+}
+
+/// Merges the filters placed right above the scan into the scan.
+fn push_down_filters(plan: LogicalPlan) -> LogicalPlan {
+    let plan = map_input(plan, push_down_filters);
+
+    match plan {
+        LogicalPlan::Filter { input, filters } => match *input {
+            LogicalPlan::Scan {
+                columns,
+                filters: mut scan_filters,
+            } if filters
+                .keys()
+                .all(|column| !scan_filters.contains_key(column)) =>
+            {
+                scan_filters.extend(filters);
+                LogicalPlan::Scan {
+                    columns,
+                    filters: scan_filters,
+                }
+            }
+            input => LogicalPlan::Filter {
+                input: Box::new(input),
+                filters,
+            },
+        },
+        plan => plan,
+    }
+}
+
+/// Makes the leaf node produce only the columns required by the nodes
+/// above it, removing the projections which became redundant.
+///
+/// The `required` columns are the ones the parent node needs, or
+/// [`None`] for the root node.
+fn prune_columns(plan: LogicalPlan, required: Option<&[String]>) -> LogicalPlan {
+    let required = required.unwrap_or(plan.output_columns()).to_vec();
+
+    match plan {
+        LogicalPlan::Scan { columns, filters } => LogicalPlan::Scan {
+            // The table evaluates the filters itself, the filtered
+            // columns needn't be read for the nodes above.
+            columns: columns
+                .into_iter()
+                .filter(|column| required.contains(column))
+                .collect(),
+            filters,
+        },
+        LogicalPlan::Empty { .. } => LogicalPlan::Empty { columns: required },
+        LogicalPlan::Filter { input, filters } => {
+            let mut input_required = required;
+            input_required.extend(filters.keys().cloned());
+
+            LogicalPlan::Filter {
+                input: Box::new(prune_columns(*input, Some(&input_required))),
+                filters,
+            }
+        }
+        LogicalPlan::Project { input, columns } => {
+            let input = prune_columns(*input, Some(&columns));
+
+            if input.output_columns() == columns.as_slice() {
+                input
+            } else {
+                LogicalPlan::Project {
+                    input: Box::new(input),
+                    columns,
+                }
+            }
+        }
+        plan @ (LogicalPlan::Sort { .. } | LogicalPlan::Limit { .. }) => {
+            map_input(plan, |input| prune_columns(input, Some(&required)))
+        }
+    }
+}
+
+/// Replaces the input node of the plan node using the function.
+fn map_input(plan: LogicalPlan, f: impl FnOnce(LogicalPlan) -> LogicalPlan) -> LogicalPlan {
+    match plan {
+        LogicalPlan::Scan { .. } | LogicalPlan::Empty { .. } => plan,
+        LogicalPlan::Filter { input, filters } => LogicalPlan::Filter {
+            input: Box::new(f(*input)),
+            filters,
+        },
+        LogicalPlan::Project { input, columns } => LogicalPlan::Project {
+            input: Box::new(f(*input)),
+            columns,
+        },
+        LogicalPlan::Sort {
+            input,
+            column,
+            order,
+        } => LogicalPlan::Sort {
+            input: Box::new(f(*input)),
+            column,
+            order,
+        },
+        LogicalPlan::Limit { input, count } => LogicalPlan::Limit {
+            input: Box::new(f(*input)),
+            count,
+        },
+    }
+}
@@ -0,0 +1,259 @@
+//! The formats the query results are printed in.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    str::FromStr,
+};
+
+use crate::{
+    error::{Error, Result},
+    result_set::{ResultSet, ResultStream},
+    table::ColumnValue,
+};
+
+mod delimited;
+mod html;
+mod ipc;
+mod json;
+mod list;
+mod markdown;
+mod parquet;
+mod table;
+
+pub use delimited::{CsvWriter, TsvWriter};
+pub use html::HtmlWriter;
+pub use ipc::ArrowIpcWriter;
+pub use json::{JsonWriter, NdjsonWriter};
+pub use list::ListWriter;
+pub use markdown::MarkdownWriter;
+pub use parquet::ParquetWriter;
+pub use table::TableWriter;
+
+/// Writes the query results in a format.
+pub trait ResultWriter {
+    /// Writes the rows of the result set to the output.
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result;
+
+    /// Writes the rows to the output as the query produces them. The rows
+    /// are collected first unless the format is written row by row.
+    fn write_stream(&self, out: &mut dyn Write, rows: ResultStream<'_>) -> Result {
+        self.write(out, &ResultSet::collect(rows)?)
+    }
+}
+
+/// The format the query results are printed in.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum OutputMode {
+    /// A boxed table with the columns aligned.
+    #[default]
+    Table,
+    /// Every row on its own line as `column: value` pairs.
+    List,
+    /// Comma-separated values with a header.
+    Csv,
+    /// Tab-separated values with a header.
+    Tsv,
+    /// A JSON array of objects.
+    Json,
+    /// A JSON object per line.
+    Ndjson,
+    /// A Markdown table.
+    Markdown,
+    /// A standalone HTML page with a table.
+    Html,
+    /// An Apache Parquet file.
+    #[value(skip)]
+    Parquet,
+    /// An Apache Arrow IPC file.
+    #[value(skip)]
+    Arrow,
+}
+
+impl OutputMode {
+    /// All the output modes.
+    pub const ALL: [OutputMode; 10] = [
+        OutputMode::Table,
+        OutputMode::List,
+        OutputMode::Csv,
+        OutputMode::Tsv,
+        OutputMode::Json,
+        OutputMode::Ndjson,
+        OutputMode::Markdown,
+        OutputMode::Html,
+        OutputMode::Parquet,
+        OutputMode::Arrow,
+    ];
+
+    /// Returns the name of the output mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputMode::Table => "table",
+            OutputMode::List => "list",
+            OutputMode::Csv => "csv",
+            OutputMode::Tsv => "tsv",
+            OutputMode::Json => "json",
+            OutputMode::Ndjson => "ndjson",
+            OutputMode::Markdown => "markdown",
+            OutputMode::Html => "html",
+            OutputMode::Parquet => "parquet",
+            OutputMode::Arrow => "arrow",
+        }
+    }
+
+    /// Returns [`true`] if the output is binary, and only written to the
+    /// files.
+    pub fn is_binary(&self) -> bool {
+        matches!(self, OutputMode::Parquet | OutputMode::Arrow)
+    }
+
+    /// Returns the output mode named after the extension of the file, if
+    /// any.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?;
+
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(extension))
+    }
+
+    /// Returns the writer of the results in the output mode.
+    pub fn writer(&self) -> Box<dyn ResultWriter> {
+        match self {
+            OutputMode::Table => Box::new(TableWriter),
+            OutputMode::List => Box::new(ListWriter),
+            OutputMode::Csv => Box::new(CsvWriter),
+            OutputMode::Tsv => Box::new(TsvWriter),
+            OutputMode::Json => Box::new(JsonWriter),
+            OutputMode::Ndjson => Box::new(NdjsonWriter),
+            OutputMode::Markdown => Box::new(MarkdownWriter),
+            OutputMode::Html => Box::new(HtmlWriter),
+            OutputMode::Parquet => Box::new(ParquetWriter::default()),
+            OutputMode::Arrow => Box::new(ArrowIpcWriter),
+        }
+    }
+}
+
+impl FromStr for OutputMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::InvalidCommand(format!("Unknown output mode: {s}")))
+    }
+}
+
+impl std::fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Prints the rows of the query to the stdout in the output mode, which
+/// mustn't be binary.
+pub fn print(mode: OutputMode, rows: ResultStream<'_>) -> Result {
+    if mode.is_binary() {
+        return Err(binary_error(mode));
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    mode.writer().write_stream(&mut stdout, rows)?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Returns the error of the binary output mode used outside of `INTO`.
+pub fn binary_error(mode: OutputMode) -> Error {
+    Error::InvalidCommand(format!(
+        "The {mode} output is binary and only written to a file with INTO"
+    ))
+}
+
+/// Writes the result set to the file in the output mode, replacing the
+/// file if it exists.
+pub fn write_file(path: &str, mode: OutputMode, result: &ResultSet) -> Result {
+    let file = File::create(path)
+        .map_err(|e| Error::Other(format!("Couldn't create {path}: {e}").into()))?;
+    let mut file = BufWriter::new(file);
+    mode.writer().write(&mut file, result)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+/// Returns the fields of the row as text.
+fn fields(row: &[ColumnValue]) -> impl Iterator<Item = String> + '_ {
+    row.iter().map(|value| match value {
+        ColumnValue::Integer(value) => value.to_string(),
+        ColumnValue::String(value) => value.as_str().to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        csv_options::CsvOptions,
+        csv_table::CsvTable,
+        filter::FilterColumns,
+        table::{ColumnType, ColumnValue, IntegerColumnType, StringColumnType},
+    };
+
+    use super::*;
+
+    /// Returns the result set of the `name` and `id` columns, a row per
+    /// name with the ids counted from 1.
+    pub fn sample(names: &[&str]) -> ResultSet {
+        let rows = names
+            .iter()
+            .zip(1..)
+            .map(|(name, id)| {
+                vec![
+                    ColumnValue::String(StringColumnType(name.to_string())),
+                    ColumnValue::Integer(IntegerColumnType(id)),
+                ]
+            })
+            .collect();
+
+        ResultSet::new(
+            vec!["name".to_string(), "id".to_string()],
+            vec![Some(ColumnType::String), Some(ColumnType::Integer)],
+            rows,
+        )
+    }
+
+    /// Returns the output of the writer for the [`sample`] of the names.
+    pub fn write_sample(writer: impl ResultWriter, names: &[&str]) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out, &sample(names)).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn writes_file() {
+        let result = sample(&["a,b"]);
+        let path = std::env::temp_dir().join(format!("prisma-test-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        write_file(path, OutputMode::Csv, &result).unwrap();
+        let written = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(written, "name,id\n\"a,b\",1\n");
+    }
+
+    #[test]
+    fn prints_text_only() {
+        let table = CsvTable::from_reader("id\n1\n".as_bytes(), &CsvOptions::default()).unwrap();
+        let filter = FilterColumns::try_from("PROJECT id FILTER id > 0").unwrap();
+
+        for mode in [OutputMode::Parquet, OutputMode::Arrow] {
+            let rows = ResultStream::execute(&filter, &table).unwrap();
+            assert!(print(mode, rows).is_err());
+        }
+        assert!(!OutputMode::Ndjson.is_binary());
+    }
+}
@@ -0,0 +1,88 @@
+//! The comma- and tab-separated values.
+
+use std::io::Write;
+
+use super::{fields, ResultWriter};
+use crate::{error::Result, result_set::ResultSet};
+
+/// Writes the rows as comma-separated values with a header, quoting the
+/// fields as described in RFC 4180.
+#[derive(Debug, Copy, Clone)]
+pub struct CsvWriter;
+
+impl ResultWriter for CsvWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        let mut writer = csv::Writer::from_writer(out);
+        writer.write_record(result.columns())?;
+
+        for row in result.iter() {
+            writer.write_record(fields(row))?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Writes the rows as tab-separated values with a header. The tabs, the
+/// line breaks and the backslashes within the fields are escaped as
+/// `\t`, `\n`, `\r` and `\\`.
+#[derive(Debug, Copy, Clone)]
+pub struct TsvWriter;
+
+impl ResultWriter for TsvWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        let header: Vec<String> = result
+            .columns()
+            .iter()
+            .map(|column| escape_tsv(column))
+            .collect();
+        writeln!(out, "{}", header.join("\t"))?;
+
+        for row in result.iter() {
+            let fields: Vec<String> = fields(row).map(|field| escape_tsv(&field)).collect();
+            writeln!(out, "{}", fields.join("\t"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes the characters which can't appear in a TSV field.
+fn escape_tsv(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::tests::write_sample;
+
+    use super::*;
+
+    fn write(writer: impl ResultWriter) -> String {
+        write_sample(writer, &["a, \"b\"\tc"])
+    }
+
+    #[test]
+    fn writes_csv() {
+        assert_eq!(write(CsvWriter), "name,id\n\"a, \"\"b\"\"\tc\",1\n");
+    }
+
+    #[test]
+    fn writes_tsv() {
+        assert_eq!(write(TsvWriter), "name\tid\na, \"b\"\\tc\t1\n");
+    }
+}
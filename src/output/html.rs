@@ -0,0 +1,96 @@
+//! The standalone HTML page.
+
+use std::io::Write;
+
+use super::{fields, ResultWriter};
+use crate::{error::Result, result_set::ResultSet};
+
+/// The beginning of the page, up to the table.
+const HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Query results</title>
+<style>
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.5em; text-align: left; }
+</style>
+</head>
+<body>
+<table>
+"#;
+
+/// The end of the page, after the table.
+const FOOTER: &str = "</table>\n</body>\n</html>\n";
+
+/// Writes the rows as a standalone HTML page with a table.
+#[derive(Debug, Copy, Clone)]
+pub struct HtmlWriter;
+
+impl ResultWriter for HtmlWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        write!(out, "{HEADER}")?;
+
+        writeln!(out, "<thead>")?;
+        write_row(out, "th", result.columns().iter().cloned())?;
+        writeln!(out, "</thead>")?;
+
+        writeln!(out, "<tbody>")?;
+        for row in result.iter() {
+            write_row(out, "td", fields(row))?;
+        }
+        writeln!(out, "</tbody>")?;
+
+        write!(out, "{FOOTER}")?;
+
+        Ok(())
+    }
+}
+
+/// Writes a table row with the cells of the tag.
+fn write_row(out: &mut dyn Write, tag: &str, cells: impl Iterator<Item = String>) -> Result {
+    write!(out, "<tr>")?;
+    for cell in cells {
+        write!(out, "<{tag}>{}</{tag}>", escape(&cell))?;
+    }
+    writeln!(out, "</tr>")?;
+
+    Ok(())
+}
+
+/// Escapes the characters which have a meaning in HTML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::tests::write_sample;
+
+    use super::*;
+
+    #[test]
+    fn writes_html() {
+        let out = write_sample(HtmlWriter, &["<b>\"Tom\" & 'Jerry'</b>"]);
+
+        let expected = format!(
+            "{HEADER}<thead>\n<tr><th>name</th><th>id</th></tr>\n</thead>\n<tbody>\n\
+             <tr><td>&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;</td><td>1</td></tr>\n\
+             </tbody>\n{FOOTER}"
+        );
+        assert_eq!(out, expected);
+    }
+}
@@ -0,0 +1,66 @@
+//! The Apache Arrow IPC files.
+
+use std::io::Write;
+
+use arrow::ipc::writer::FileWriter;
+
+use super::ResultWriter;
+use crate::{error::Result, result_set::ResultSet};
+
+/// Writes the rows as an Arrow IPC file holding a single record batch.
+///
+/// The integer columns are written as 64-bit integers and the rest as
+/// UTF-8 strings, the empty values being nulls.
+#[derive(Debug, Copy, Clone)]
+pub struct ArrowIpcWriter;
+
+impl ResultWriter for ArrowIpcWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        let batch = result.to_record_batch()?;
+
+        let mut writer = FileWriter::try_new(out, &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use arrow::ipc::reader::FileReader;
+
+    use crate::table::{ColumnType, ColumnValue, IntegerColumnType, StringColumnType};
+
+    use super::*;
+
+    #[test]
+    fn writes_arrow_ipc() {
+        let result = ResultSet::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![Some(ColumnType::Integer), Some(ColumnType::String)],
+            vec![
+                vec![
+                    ColumnValue::Integer(IntegerColumnType(1)),
+                    ColumnValue::String(StringColumnType("a".to_string())),
+                ],
+                vec![
+                    ColumnValue::Integer(IntegerColumnType(2)),
+                    ColumnValue::null(),
+                ],
+            ],
+        );
+
+        let mut out = Vec::new();
+        ArrowIpcWriter.write(&mut out, &result).unwrap();
+
+        let batches = FileReader::try_new(Cursor::new(out), None)
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(ResultSet::try_from(&batches[0]).unwrap(), result);
+    }
+}
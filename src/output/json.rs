@@ -0,0 +1,154 @@
+//! The JSON array and the newline-delimited JSON.
+
+use std::io::Write;
+
+use serde_json::Value;
+
+use super::ResultWriter;
+use crate::{
+    error::Result,
+    result_set::{ResultSet, ResultStream},
+    table::{ColumnType, ColumnValue},
+};
+
+/// Writes the rows as a JSON array of objects, one object per line.
+#[derive(Debug, Copy, Clone)]
+pub struct JsonWriter;
+
+impl ResultWriter for JsonWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        write!(out, "[")?;
+
+        for (i, row) in result.iter().enumerate() {
+            writeln!(out, "{}", if i == 0 { "" } else { "," })?;
+            write_object(out, result.columns(), result.column_types(), row)?;
+        }
+
+        writeln!(out, "\n]")?;
+
+        Ok(())
+    }
+}
+
+/// Writes every row as a JSON object on its own line.
+#[derive(Debug, Copy, Clone)]
+pub struct NdjsonWriter;
+
+impl ResultWriter for NdjsonWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        for row in result.iter() {
+            write_object(out, result.columns(), result.column_types(), row)?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_stream(&self, out: &mut dyn Write, mut rows: ResultStream<'_>) -> Result {
+        let columns = rows.columns().to_vec();
+        let types = rows.column_types().to_vec();
+
+        rows.try_for_each(|row| {
+            write_object(out, &columns, &types, &row?)?;
+            writeln!(out)?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Writes the row as a JSON object, the keys in the order of the columns.
+/// The integers are written as numbers. The empty strings are written as
+/// `null` unless their column holds only strings, where they are values.
+fn write_object(
+    out: &mut dyn Write,
+    columns: &[String],
+    types: &[Option<ColumnType>],
+    row: &[ColumnValue],
+) -> Result {
+    write!(out, "{{")?;
+
+    for (i, ((column, column_type), value)) in columns.iter().zip(types).zip(row).enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+
+        let value = match value {
+            value if value.is_null() && *column_type != Some(ColumnType::String) => Value::Null,
+            ColumnValue::Integer(value) => Value::from(value.0),
+            ColumnValue::String(value) => Value::from(value.as_str()),
+        };
+
+        serde_json::to_writer(&mut *out, column)?;
+        write!(out, ":")?;
+        serde_json::to_writer(&mut *out, &value)?;
+    }
+
+    write!(out, "}}")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        csv_options::CsvOptions, csv_table::CsvTable, filter::FilterColumns,
+        output::tests::write_sample,
+    };
+
+    use super::*;
+
+    fn write(writer: impl ResultWriter) -> String {
+        write_sample(writer, &["say \"hi\"\n", ""])
+    }
+
+    #[test]
+    fn writes_json() {
+        assert_eq!(
+            write(JsonWriter),
+            "[\n{\"name\":\"say \\\"hi\\\"\\n\",\"id\":1},\n{\"name\":\"\",\"id\":2}\n]\n"
+        );
+    }
+
+    #[test]
+    fn writes_ndjson() {
+        assert_eq!(
+            write(NdjsonWriter),
+            "{\"name\":\"say \\\"hi\\\"\\n\",\"id\":1}\n{\"name\":\"\",\"id\":2}\n"
+        );
+    }
+
+    #[test]
+    fn writes_nulls() {
+        let columns = ["name".to_string(), "id".to_string()];
+        let empty = ColumnValue::null();
+
+        let mut out = Vec::new();
+        write_object(&mut out, &columns, &[None, None], &[empty.clone(), empty]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"name\":null,\"id\":null}"
+        );
+    }
+
+    #[test]
+    fn streams_ndjson() {
+        let table = CsvTable::from_reader(
+            "id,name\n1,a\n2,b\n3,c\n".as_bytes(),
+            &CsvOptions::default(),
+        )
+        .unwrap();
+        let filter = FilterColumns::try_from("PROJECT name, id FILTER id > 1").unwrap();
+
+        let mut out = Vec::new();
+        NdjsonWriter
+            .write_stream(&mut out, ResultStream::execute(&filter, &table).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"name\":\"b\",\"id\":2}\n{\"name\":\"c\",\"id\":3}\n"
+        );
+    }
+}
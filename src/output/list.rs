@@ -0,0 +1,40 @@
+//! The `column: value` pairs.
+
+use std::io::Write;
+
+use super::ResultWriter;
+use crate::{
+    error::Result,
+    result_set::{ResultSet, ResultStream},
+    table::ColumnValue,
+};
+
+/// Writes every row on its own line as `column: value` pairs.
+#[derive(Debug, Copy, Clone)]
+pub struct ListWriter;
+
+impl ResultWriter for ListWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        for row in result.iter() {
+            write_row(out, result.columns(), row)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_stream(&self, out: &mut dyn Write, mut rows: ResultStream<'_>) -> Result {
+        let columns = rows.columns().to_vec();
+
+        rows.try_for_each(|row| write_row(out, &columns, &row?))
+    }
+}
+
+/// Writes the row on its own line.
+fn write_row(out: &mut dyn Write, columns: &[String], row: &[ColumnValue]) -> Result {
+    for (column, value) in columns.iter().zip(row) {
+        write!(out, "{column}: {value} ")?;
+    }
+    writeln!(out)?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! The Markdown table.
+
+use std::io::Write;
+
+use super::{fields, ResultWriter};
+use crate::{error::Result, result_set::ResultSet};
+
+/// Writes the rows as a Markdown table with a header.
+#[derive(Debug, Copy, Clone)]
+pub struct MarkdownWriter;
+
+impl ResultWriter for MarkdownWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        let columns = result.columns();
+        let header: Vec<String> = columns.iter().map(|column| escape(column)).collect();
+        writeln!(out, "| {} |", header.join(" | "))?;
+        writeln!(out, "|{}", " --- |".repeat(columns.len()))?;
+
+        for row in result.iter() {
+            let cells: Vec<String> = fields(row).map(|field| escape(&field)).collect();
+            writeln!(out, "| {} |", cells.join(" | "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes the characters which would be taken for the Markdown syntax.
+/// The line breaks, which would end the table row, become `<br>`.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' | '|' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' => {}
+            '\n' => escaped.push_str("<br>"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::tests::write_sample;
+
+    use super::*;
+
+    #[test]
+    fn writes_markdown() {
+        assert_eq!(
+            write_sample(MarkdownWriter, &["a|b *c*\nd"]),
+            "| name | id |\n| --- | --- |\n| a\\|b \\*c\\*<br>d | 1 |\n"
+        );
+    }
+}
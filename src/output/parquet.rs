@@ -0,0 +1,189 @@
+//! The Apache Parquet files.
+
+use std::{io::Write, sync::Arc};
+
+use parquet::{
+    basic::{Compression, LogicalType, Repetition, Type as PhysicalType},
+    data_type::{ByteArray, ByteArrayType, Int64Type},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::types::Type,
+};
+
+use super::{fields, ResultWriter};
+use crate::{
+    error::Result,
+    result_set::ResultSet,
+    table::{ColumnType, ColumnValue},
+};
+
+/// The number of rows in a row group written by default.
+pub const ROW_GROUP_SIZE: usize = 1024 * 1024;
+
+/// Writes the rows as a Parquet file, compressed with Snappy.
+///
+/// The integer columns are written as 64-bit integers and the rest as
+/// UTF-8 strings, the empty values being nulls.
+#[derive(Debug, Copy, Clone)]
+pub struct ParquetWriter {
+    /// The maximum number of rows in a row group.
+    pub row_group_size: usize,
+}
+
+impl Default for ParquetWriter {
+    fn default() -> Self {
+        Self {
+            row_group_size: ROW_GROUP_SIZE,
+        }
+    }
+}
+
+impl ResultWriter for ParquetWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        let fields = result
+            .columns()
+            .iter()
+            .zip(result.column_types())
+            .map(|(name, column_type)| {
+                let builder = match column_type {
+                    Some(ColumnType::Integer) => {
+                        Type::primitive_type_builder(name, PhysicalType::INT64)
+                    }
+                    _ => Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
+                        .with_logical_type(Some(LogicalType::String)),
+                };
+
+                builder
+                    .with_repetition(Repetition::OPTIONAL)
+                    .build()
+                    .map(Arc::new)
+            })
+            .collect::<Result<_, _>>()?;
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+
+        // The writer requires a `Send` output, so the file is put
+        // together in memory first.
+        let mut buffer = Vec::new();
+        let mut writer =
+            SerializedFileWriter::new(&mut buffer, Arc::new(schema), Arc::new(properties))?;
+
+        let rows: Vec<_> = result.iter().collect();
+        for rows in rows.chunks(self.row_group_size.max(1)) {
+            let mut row_group = writer.next_row_group()?;
+
+            for (column_idx, column_type) in result.column_types().iter().enumerate() {
+                let Some(mut column) = row_group.next_column()? else {
+                    break;
+                };
+                let values = rows.iter().map(|row| &row[column_idx]);
+                let definition_levels: Vec<i16> = values
+                    .clone()
+                    .map(|value| i16::from(!value.is_null()))
+                    .collect();
+
+                match column_type {
+                    Some(ColumnType::Integer) => {
+                        let values: Vec<i64> = values
+                            .filter_map(|value| value.as_integer().map(|value| value.0))
+                            .collect();
+                        column.typed::<Int64Type>().write_batch(
+                            &values,
+                            Some(&definition_levels),
+                            None,
+                        )?;
+                    }
+                    _ => {
+                        let values: Vec<ByteArray> = values
+                            .filter(|value| !value.is_null())
+                            .map(|value| text(value).into_bytes().into())
+                            .collect();
+                        column.typed::<ByteArrayType>().write_batch(
+                            &values,
+                            Some(&definition_levels),
+                            None,
+                        )?;
+                    }
+                }
+
+                column.close()?;
+            }
+
+            row_group.close()?;
+        }
+
+        writer.close()?;
+        out.write_all(&buffer)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the value as text.
+fn text(value: &ColumnValue) -> String {
+    fields(std::slice::from_ref(value))
+        .next()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+    };
+
+    use crate::table::{IntegerColumnType, StringColumnType};
+
+    use super::*;
+
+    #[test]
+    fn writes_parquet() {
+        let id = |id| ColumnValue::Integer(IntegerColumnType(id));
+        let name = |name: &str| ColumnValue::String(StringColumnType(name.to_string()));
+        let result = ResultSet::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![Some(ColumnType::Integer), None],
+            vec![
+                vec![id(1), name("a")],
+                vec![name(""), id(2)],
+                vec![id(3), name("")],
+            ],
+        );
+
+        let mut out = Vec::new();
+        ParquetWriter { row_group_size: 2 }
+            .write(&mut out, &result)
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("prisma-test-{}.parquet", std::process::id()));
+        std::fs::write(&path, out).unwrap();
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(reader.num_row_groups(), 2);
+
+        let rows: Vec<Vec<Field>> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .into_columns()
+                    .into_iter()
+                    .map(|(_, field)| field)
+                    .collect()
+            })
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![Field::Long(1), Field::Str("a".to_string())],
+                vec![Field::Null, Field::Str("2".to_string())],
+                vec![Field::Long(3), Field::Null],
+            ]
+        );
+    }
+}
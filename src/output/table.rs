@@ -0,0 +1,165 @@
+//! The boxed table with the columns aligned.
+
+use std::io::Write;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::ResultWriter;
+use crate::{error::Result, result_set::ResultSet, table::ColumnValue};
+
+/// The widest a cell of the table may be, in terminal columns.
+const MAX_CELL_WIDTH: usize = 40;
+
+/// Writes the rows as a boxed table with a header and a row count footer.
+#[derive(Debug, Copy, Clone)]
+pub struct TableWriter;
+
+impl ResultWriter for TableWriter {
+    fn write(&self, out: &mut dyn Write, result: &ResultSet) -> Result {
+        write!(out, "{}", render_table(result))?;
+
+        Ok(())
+    }
+}
+
+/// Renders the rows as a boxed table with a header and a row count
+/// footer. The integers are aligned to the right, and the cells too wide
+/// are truncated.
+fn render_table(result: &ResultSet) -> String {
+    let cells: Vec<Vec<(String, bool)>> = result
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|value| match value {
+                    ColumnValue::Integer(value) => (value.to_string(), true),
+                    ColumnValue::String(value) => (truncate(value), false),
+                })
+                .collect()
+        })
+        .collect();
+
+    let headers: Vec<String> = result
+        .columns()
+        .iter()
+        .map(|column| truncate(column))
+        .collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            cells
+                .iter()
+                .map(|row| row[i].0.width())
+                .chain([header.width()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let border = |left: &str, middle: &str, right: &str| {
+        let lines: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{left}{}{right}\n", lines.join(middle))
+    };
+    let line = |cells: &mut dyn Iterator<Item = (&str, bool)>| {
+        let cells: Vec<String> = cells
+            .zip(&widths)
+            .map(|((cell, right_aligned), width)| {
+                let padding = " ".repeat(width - cell.width());
+                if right_aligned {
+                    format!(" {padding}{cell} ")
+                } else {
+                    format!(" {cell}{padding} ")
+                }
+            })
+            .collect();
+        format!("│{}│\n", cells.join("│"))
+    };
+
+    let mut table = border("┌", "┬", "┐");
+    table += &line(&mut headers.iter().map(|header| (header.as_str(), false)));
+    table += &border("├", "┼", "┤");
+    for row in &cells {
+        table += &line(&mut row.iter().map(|(cell, right)| (cell.as_str(), *right)));
+    }
+    table += &border("└", "┴", "┘");
+
+    let noun = if result.len() == 1 { "row" } else { "rows" };
+    table += &format!("{} {noun}\n", result.len());
+
+    table
+}
+
+/// Returns the text fitting in [`MAX_CELL_WIDTH`] terminal columns, ending
+/// with `…` if it was cut. The control characters are replaced with
+/// spaces so that they don't break the table.
+fn truncate(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+
+    if text.width() <= MAX_CELL_WIDTH {
+        return text;
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or_default();
+        // Leave room for the ellipsis.
+        if width + char_width > MAX_CELL_WIDTH - 1 {
+            break;
+        }
+        truncated.push(c);
+        width += char_width;
+    }
+    truncated.push('…');
+
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::table::{IntegerColumnType, StringColumnType};
+
+    use super::*;
+
+    #[test]
+    fn renders_table() {
+        let id = ColumnValue::Integer(IntegerColumnType(42));
+        let name = ColumnValue::String(StringColumnType("日本".to_string()));
+        let long = ColumnValue::String(StringColumnType("x".repeat(50)));
+        let empty = ColumnValue::null();
+        let result = ResultSet::new(
+            vec!["name".to_string(), "id".to_string()],
+            vec![None, None],
+            vec![vec![name, id], vec![long, empty]],
+        );
+
+        let truncated = format!("{}…", "x".repeat(MAX_CELL_WIDTH - 1));
+        let line = "─".repeat(MAX_CELL_WIDTH + 2);
+        let expected = [
+            format!("┌{line}┬────┐"),
+            format!("│ name{} │ id │", " ".repeat(MAX_CELL_WIDTH - 4)),
+            format!("├{line}┼────┤"),
+            format!("│ 日本{} │ 42 │", " ".repeat(MAX_CELL_WIDTH - 4)),
+            format!("│ {truncated} │    │"),
+            format!("└{line}┴────┘"),
+            "2 rows".to_string(),
+        ];
+
+        assert_eq!(render_table(&result), expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn renders_empty_table() {
+        assert_eq!(
+            render_table(&ResultSet::new(
+                vec!["col1".to_string()],
+                vec![None],
+                vec![]
+            )),
+            "┌──────┐\n│ col1 │\n├──────┤\n└──────┘\n0 rows\n"
+        );
+    }
+}
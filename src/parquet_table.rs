@@ -0,0 +1,405 @@
+//! The tables backed by the Apache Parquet files.
+
+use std::{collections::HashMap, error::Error, fs::File};
+
+use parquet::{
+    basic::{ConvertedType, Type as PhysicalType},
+    file::{
+        reader::{FileReader, SerializedFileReader},
+        statistics::Statistics,
+    },
+    record::Field,
+    schema::types::{ColumnDescriptor, Type, TypePtr},
+};
+
+use crate::{
+    column::Column,
+    filter::{ApplyTableFilterByValue, FilterColumns, FilterQueryIterator},
+    index::AccessPath,
+    table::{AsTable, ColumnType, ColumnValue, IntegerColumnType, LoadOnDemand, StringColumnType},
+    zone_map::ZoneMap,
+};
+
+/// The table of a Parquet file, whose top-level fields are the columns,
+/// read on demand.
+#[derive(Debug, Clone)]
+pub struct ParquetTable {
+    /// The path of the file.
+    path: String,
+    /// The top-level fields of the file schema.
+    fields: Vec<TypePtr>,
+    /// The column names in the order of the file schema.
+    columns: Vec<String>,
+    /// The type of every column, if all its values are of the same type.
+    column_types: HashMap<String, Option<ColumnType>>,
+    /// The zone maps of every row group, by column name, built from the
+    /// statistics ordered the same way as the values.
+    zone_maps: HashMap<String, Vec<Option<ZoneMap>>>,
+    /// The number of row groups in the file.
+    row_group_count: usize,
+    /// The number of rows in the file.
+    row_count: usize,
+    /// The names and the values of the loaded columns, in the order of
+    /// the file schema.
+    data: Vec<(String, Column)>,
+    /// The number of row groups skipped when loading the values.
+    skipped_row_groups: usize,
+}
+
+/// The way the values of a top-level column are read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ValueKind {
+    /// The signed integers, ordered by the statistics.
+    SignedInteger,
+    /// The unsigned integers fitting into `i64`.
+    UnsignedInteger,
+    /// The UTF-8 strings, ordered by the statistics.
+    Utf8,
+    /// Any other values, read as text.
+    Text,
+}
+
+impl ValueKind {
+    /// Returns the way the values of the column are read.
+    fn of(column: &ColumnDescriptor) -> Self {
+        if column.max_rep_level() > 0 {
+            return ValueKind::Text;
+        }
+
+        match (column.physical_type(), column.converted_type()) {
+            (
+                PhysicalType::INT32,
+                ConvertedType::NONE
+                | ConvertedType::INT_8
+                | ConvertedType::INT_16
+                | ConvertedType::INT_32,
+            )
+            | (PhysicalType::INT64, ConvertedType::NONE | ConvertedType::INT_64) => {
+                ValueKind::SignedInteger
+            }
+            (
+                PhysicalType::INT32,
+                ConvertedType::UINT_8 | ConvertedType::UINT_16 | ConvertedType::UINT_32,
+            ) => ValueKind::UnsignedInteger,
+            (
+                PhysicalType::BYTE_ARRAY,
+                ConvertedType::UTF8 | ConvertedType::ENUM | ConvertedType::JSON,
+            ) => ValueKind::Utf8,
+            _ => ValueKind::Text,
+        }
+    }
+}
+
+impl ParquetTable {
+    /// Opens the Parquet file, reading its schema and the statistics of
+    /// its row groups.
+    pub fn from_parquet(file_path: &str) -> Result<Self, Box<dyn Error>> {
+        let reader = SerializedFileReader::new(File::open(file_path)?)?;
+        let metadata = reader.metadata();
+        let schema = metadata.file_metadata().schema_descr();
+
+        let fields = schema.root_schema().get_fields().to_vec();
+        let columns: Vec<String> = fields.iter().map(|field| field.name().to_owned()).collect();
+
+        // The nested columns are read as text.
+        let mut column_types: HashMap<String, Option<ColumnType>> = columns
+            .iter()
+            .map(|column| (column.clone(), Some(ColumnType::String)))
+            .collect();
+        let mut zone_maps = HashMap::new();
+
+        for (leaf_idx, column) in schema.columns().iter().enumerate() {
+            if column.path().parts().len() != 1 {
+                continue;
+            }
+
+            let nullable = column.max_def_level() > 0;
+            let statistics: Vec<Option<&Statistics>> = metadata
+                .row_groups()
+                .iter()
+                .map(|row_group| row_group.column(leaf_idx).statistics())
+                .collect();
+            let has_nulls = nullable
+                && statistics
+                    .iter()
+                    .any(|statistics| statistics.and_then(Statistics::null_count_opt) != Some(0));
+
+            let kind = ValueKind::of(column);
+            // The nulls are read as empty strings, so the integer columns
+            // with nulls are of mixed types.
+            let column_type = match kind {
+                ValueKind::SignedInteger | ValueKind::UnsignedInteger if has_nulls => None,
+                ValueKind::SignedInteger | ValueKind::UnsignedInteger => Some(ColumnType::Integer),
+                ValueKind::Utf8 | ValueKind::Text => Some(ColumnType::String),
+            };
+            column_types.insert(column.name().to_owned(), column_type);
+
+            if matches!(kind, ValueKind::SignedInteger | ValueKind::Utf8) {
+                let column_zone_maps = statistics
+                    .into_iter()
+                    .map(|statistics| statistics.and_then(|s| zone_map(s, nullable)))
+                    .collect();
+                zone_maps.insert(column.name().to_owned(), column_zone_maps);
+            }
+        }
+
+        Ok(Self {
+            path: file_path.to_owned(),
+            fields,
+            columns,
+            column_types,
+            zone_maps,
+            row_group_count: metadata.num_row_groups(),
+            row_count: usize::try_from(metadata.file_metadata().num_rows())?,
+            data: Vec::new(),
+            skipped_row_groups: 0,
+        })
+    }
+
+    /// Returns the number of rows in the file.
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+}
+
+impl LoadOnDemand for ParquetTable {
+    /// Loads the values from the row groups whose statistics may satisfy
+    /// the filters only.
+    fn load(&self, filter_columns: &FilterColumns) -> crate::error::Result<Self> {
+        let referenced = filter_columns.referenced_columns(&self.columns)?;
+        let fields: Vec<TypePtr> = self
+            .fields
+            .iter()
+            .filter(|field| referenced.iter().any(|column| *column == field.name()))
+            .cloned()
+            .collect();
+        let projection = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?;
+
+        let row_groups: Vec<usize> = (0..self.row_group_count)
+            .filter(|&row_group| {
+                filter_columns.filters.iter().all(|(column, filter)| {
+                    self.zone_maps
+                        .get(column)
+                        .and_then(|zone_maps| zone_maps[row_group].as_ref())
+                        .is_none_or(|zone_map| zone_map.may_match(filter))
+                })
+            })
+            .collect();
+
+        let mut values: Vec<(String, Vec<ColumnValue>)> = projection
+            .get_fields()
+            .iter()
+            .map(|field| (field.name().to_owned(), Vec::new()))
+            .collect();
+
+        let reader = SerializedFileReader::new(File::open(&self.path)?)?;
+        for &row_group in &row_groups {
+            let row_group = reader.get_row_group(row_group)?;
+            let rows = row_group.get_row_iter(Some(projection.clone()))?;
+
+            for row in rows {
+                for ((_, values), (_, field)) in values.iter_mut().zip(row?.into_columns()) {
+                    values.push(to_column_value(field));
+                }
+            }
+        }
+
+        Ok(Self {
+            data: values
+                .into_iter()
+                .map(|(name, values)| (name, Column::from_values(values)))
+                .collect(),
+            skipped_row_groups: self.row_group_count - row_groups.len(),
+            ..self.clone()
+        })
+    }
+}
+
+/// Builds the zone map of a column of a row group from its statistics,
+/// if they hold the smallest and the largest values.
+///
+/// The nulls are read as empty strings, so the zone map covers them as
+/// well. Without their count, the nulls are assumed to be there.
+fn zone_map(statistics: &Statistics, nullable: bool) -> Option<ZoneMap> {
+    let integer = |value: i64| ColumnValue::Integer(IntegerColumnType(value));
+    let string = |value: &str| ColumnValue::String(StringColumnType(value.to_owned()));
+
+    let (min, max) = match statistics {
+        Statistics::Int32(statistics) => (
+            integer((*statistics.min_opt()?).into()),
+            integer((*statistics.max_opt()?).into()),
+        ),
+        Statistics::Int64(statistics) => (
+            integer(*statistics.min_opt()?),
+            integer(*statistics.max_opt()?),
+        ),
+        Statistics::ByteArray(statistics) => (
+            string(statistics.min_opt()?.as_utf8().ok()?),
+            string(statistics.max_opt()?.as_utf8().ok()?),
+        ),
+        _ => return None,
+    };
+
+    let null_count = match statistics.null_count_opt() {
+        _ if !nullable => 0,
+        Some(null_count) => usize::try_from(null_count).ok()?,
+        None => 1,
+    };
+
+    let mut zone_map = ZoneMap {
+        min,
+        max,
+        null_count,
+    };
+    if null_count > 0 {
+        zone_map.min = zone_map.min.min(ColumnValue::null());
+        zone_map.max = zone_map.max.max(ColumnValue::null());
+    }
+
+    Some(zone_map)
+}
+
+/// Converts the Parquet value into a column value.
+fn to_column_value(field: Field) -> ColumnValue {
+    let integer = |value: i64| ColumnValue::Integer(IntegerColumnType(value));
+
+    match field {
+        Field::Null => ColumnValue::null(),
+        Field::Byte(value) => integer(value.into()),
+        Field::Short(value) => integer(value.into()),
+        Field::Int(value) => integer(value.into()),
+        Field::Long(value) => integer(value),
+        Field::UByte(value) => integer(value.into()),
+        Field::UShort(value) => integer(value.into()),
+        Field::UInt(value) => integer(value.into()),
+        Field::Str(value) => ColumnValue::String(StringColumnType(value)),
+        Field::Bytes(value) => ColumnValue::String(StringColumnType(
+            String::from_utf8_lossy(value.data()).into_owned(),
+        )),
+        field => ColumnValue::String(StringColumnType(field.to_string())),
+    }
+}
+
+impl ApplyTableFilterByValue<'_> for ParquetTable {
+    fn apply_filter(&self, filter: &FilterColumns) -> FilterQueryIterator<'_> {
+        FilterQueryIterator::new(self.get_rows(), filter.clone())
+    }
+
+    /// Reports the row groups skipped when loading the values as the
+    /// skipped blocks.
+    fn access_path(&self, _filter: &FilterColumns) -> AccessPath {
+        if self.skipped_row_groups == 0 {
+            return AccessPath::Full;
+        }
+
+        AccessPath::Pruned {
+            rows: std::iter::once(0..self.data.first().map_or(0, |(_, column)| column.len()))
+                .collect(),
+            skipped_blocks: self.skipped_row_groups,
+            total_blocks: self.row_group_count,
+        }
+    }
+}
+
+impl AsTable for ParquetTable {
+    fn get_name(&self) -> &str {
+        "Parquet Table"
+    }
+
+    fn get_columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.data
+            .iter()
+            .map(|(name, column)| (name.as_str(), column))
+    }
+
+    fn get_column_names(&self) -> impl Iterator<Item = &String> {
+        self.columns.iter()
+    }
+
+    fn get_column_type(&self, name: &str) -> Option<ColumnType> {
+        self.column_types.get(name).copied().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        output::{ParquetWriter, ResultWriter},
+        result_set::ResultSet,
+    };
+
+    use super::*;
+
+    #[test]
+    fn load_skipping_row_groups() {
+        let rows = (1..=5)
+            .map(|id| {
+                vec![
+                    ColumnValue::Integer(IntegerColumnType(id)),
+                    ColumnValue::String(StringColumnType(format!("name{id}"))),
+                    if id == 2 {
+                        ColumnValue::null()
+                    } else {
+                        ColumnValue::Integer(IntegerColumnType(id * 10))
+                    },
+                ]
+            })
+            .collect();
+        let result = ResultSet::new(
+            vec!["id".to_string(), "name".to_string(), "amount".to_string()],
+            vec![
+                Some(ColumnType::Integer),
+                Some(ColumnType::String),
+                Some(ColumnType::Integer),
+            ],
+            rows,
+        );
+        let mut out = Vec::new();
+        ParquetWriter { row_group_size: 2 }
+            .write(&mut out, &result)
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("prisma-test-{}-load.parquet", std::process::id()));
+        std::fs::write(&path, out).unwrap();
+        let table = ParquetTable::from_parquet(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(table.row_count(), 5);
+        assert_eq!(table.get_column_type("id"), Some(ColumnType::Integer));
+        assert_eq!(table.get_column_type("name"), Some(ColumnType::String));
+        assert_eq!(table.get_column_type("amount"), None);
+
+        let filter_columns = FilterColumns::try_from("PROJECT name FILTER id > 3").unwrap();
+        let loaded = table.load(&filter_columns).unwrap();
+        assert_eq!(
+            loaded
+                .get_columns()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["id", "name"]
+        );
+        assert_eq!(loaded.get_rows().count(), 3);
+        assert_eq!(
+            loaded.access_path(&filter_columns).to_string(),
+            "Full scan skipping 1 of 3 blocks by zone maps"
+        );
+
+        let names = table.execute(filter_columns).unwrap();
+        let nulls = table.execute(r#"PROJECT id FILTER amount = """#).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            names.into_rows(),
+            vec![
+                vec![ColumnValue::String(StringColumnType("name4".to_string()))],
+                vec![ColumnValue::String(StringColumnType("name5".to_string()))],
+            ]
+        );
+        assert_eq!(
+            nulls.into_rows(),
+            vec![vec![ColumnValue::Integer(IntegerColumnType(2))]]
+        );
+    }
+}
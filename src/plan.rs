@@ -0,0 +1,369 @@
+//! The logical query plan and its execution.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{FilterError, Result},
+    filter::{
+        row_matches, ApplyTableFilterByValue, FilterByValue, FilterColumns, FilterQueryIterator,
+        SortOrder,
+    },
+    table::{AsTable, ColumnValue},
+};
+
+/// A single row produced by a plan node.
+pub type Row<'a> = HashMap<String, &'a ColumnValue>;
+
+/// The rows produced by a plan node.
+pub type Rows<'a> = Box<dyn Iterator<Item = Row<'a>> + 'a>;
+
+/// A node of the logical query plan. Every node but the scan takes the
+/// rows produced by its input node.
+#[derive(Debug, Clone)]
+pub enum LogicalPlan {
+    /// Reads the columns of the rows of the table satisfying the filters
+    /// pushed down into it.
+    Scan {
+        /// The columns to read.
+        columns: Vec<String>,
+        /// The filters evaluated by the table itself.
+        filters: HashMap<String, FilterByValue>,
+    },
+    /// Produces no rows at all, for example when a filter can never be
+    /// satisfied.
+    Empty {
+        /// The columns the rows would have had.
+        columns: Vec<String>,
+    },
+    /// Keeps the rows satisfying all the filters.
+    Filter {
+        /// The input node.
+        input: Box<LogicalPlan>,
+        /// The filters to satisfy.
+        filters: HashMap<String, FilterByValue>,
+    },
+    /// Keeps only the listed columns of the rows.
+    Project {
+        /// The input node.
+        input: Box<LogicalPlan>,
+        /// The columns to keep.
+        columns: Vec<String>,
+    },
+    /// Sorts the rows by a column.
+    Sort {
+        /// The input node.
+        input: Box<LogicalPlan>,
+        /// The column to sort by.
+        column: String,
+        /// The order of the rows.
+        order: SortOrder,
+    },
+    /// Keeps at most the given number of the first rows.
+    Limit {
+        /// The input node.
+        input: Box<LogicalPlan>,
+        /// The maximum number of rows.
+        count: usize,
+    },
+}
+
+impl LogicalPlan {
+    /// Builds the plan of the query against the table.
+    ///
+    /// The plan is built in the canonical Scan → Filter → Project → Sort
+    /// → Limit shape, reading all the columns of the table. It is up to
+    /// the [`crate::optimizer`] to make it efficient.
+    pub fn build(query: &FilterColumns, table: &impl AsTable) -> Result<Self> {
+        let table_columns: Vec<String> = table.get_column_names().cloned().collect();
+        query.referenced_columns(&table_columns)?;
+
+        let mut plan = LogicalPlan::Scan {
+            columns: table_columns,
+            filters: HashMap::new(),
+        };
+
+        if !query.filters.is_empty() {
+            plan = LogicalPlan::Filter {
+                input: Box::new(plan),
+                filters: query.filters.clone(),
+            };
+        }
+
+        plan = LogicalPlan::Project {
+            input: Box::new(plan),
+            columns: query.output_columns.clone(),
+        };
+
+        if let Some(order_by) = &query.order_by {
+            if !query.output_columns.contains(&order_by.column) {
+                return Err(FilterError::Parse(format!(
+                    "The rows can only be ordered by a projected column, not {}",
+                    order_by.column
+                ))
+                .into());
+            }
+
+            plan = LogicalPlan::Sort {
+                input: Box::new(plan),
+                column: order_by.column.clone(),
+                order: order_by.order,
+            };
+        }
+
+        if let Some(count) = query.limit {
+            plan = LogicalPlan::Limit {
+                input: Box::new(plan),
+                count,
+            };
+        }
+
+        Ok(plan)
+    }
+
+    /// Builds the plan of the query against the table and optimizes it.
+    pub fn optimized(query: &FilterColumns, table: &impl AsTable) -> Result<Self> {
+        Ok(crate::optimizer::optimize(
+            Self::build(query, table)?,
+            table,
+        ))
+    }
+
+    /// Returns the input node, unless it is a leaf node.
+    pub fn input(&self) -> Option<&LogicalPlan> {
+        match self {
+            Self::Scan { .. } | Self::Empty { .. } => None,
+            Self::Filter { input, .. }
+            | Self::Project { input, .. }
+            | Self::Sort { input, .. }
+            | Self::Limit { input, .. } => Some(input),
+        }
+    }
+
+    /// Returns the columns of the rows produced by the node.
+    pub fn output_columns(&self) -> &[String] {
+        match self {
+            Self::Scan { columns, .. }
+            | Self::Empty { columns }
+            | Self::Project { columns, .. } => columns,
+            Self::Filter { input, .. } | Self::Sort { input, .. } | Self::Limit { input, .. } => {
+                input.output_columns()
+            }
+        }
+    }
+
+    /// Returns the filter the scan node passes to the table, if the plan
+    /// has a scan node.
+    pub fn scan_filter(&self) -> Option<FilterColumns> {
+        match self {
+            Self::Scan { columns, filters } => Some(FilterColumns {
+                output_columns: columns.clone(),
+                filters: filters.clone(),
+                ..Default::default()
+            }),
+            _ => self.input()?.scan_filter(),
+        }
+    }
+
+    /// Executes the plan against the table, returning the produced rows.
+    pub fn execute<'a, T>(&self, table: &'a T) -> Rows<'a>
+    where
+        T: ApplyTableFilterByValue<'a>,
+    {
+        self.execute_node(table, None)
+    }
+
+    /// Executes the plan against the table, collecting the statistics of
+    /// every node while the returned rows are consumed.
+    ///
+    /// The statistics are listed from the root node down to the leaf.
+    pub fn execute_analyzed<'a, T>(&self, table: &'a T) -> (Rows<'a>, Vec<Rc<RefCell<NodeStats>>>)
+    where
+        T: ApplyTableFilterByValue<'a>,
+    {
+        let mut stats = Vec::new();
+        let rows = self.execute_node(table, Some(&mut stats));
+
+        (rows, stats)
+    }
+
+    /// Executes the node, pushing its statistics and the ones of its
+    /// inputs, if they are collected.
+    fn execute_node<'a, T>(
+        &self,
+        table: &'a T,
+        mut stats: Option<&mut Vec<Rc<RefCell<NodeStats>>>>,
+    ) -> Rows<'a>
+    where
+        T: ApplyTableFilterByValue<'a>,
+    {
+        let node_stats = stats.as_mut().map(|stats| {
+            let node_stats = Rc::new(RefCell::new(NodeStats::default()));
+            stats.push(Rc::clone(&node_stats));
+            node_stats
+        });
+
+        let rows: Rows<'a> = match self {
+            Self::Scan { .. } => {
+                let filter = self.scan_filter().unwrap_or_default();
+                let rows = table.apply_filter(&filter);
+
+                return match node_stats {
+                    Some(node_stats) => Box::new(Instrumented {
+                        inner: rows,
+                        stats: node_stats,
+                        rows_scanned: Some(FilterQueryIterator::rows_scanned),
+                    }),
+                    None => Box::new(rows),
+                };
+            }
+            Self::Empty { .. } => Box::new(std::iter::empty()),
+            Self::Filter { input, filters } => {
+                let filters = filters.clone();
+                Box::new(
+                    input
+                        .execute_node(table, stats)
+                        .filter(move |row| row_matches(row, &filters)),
+                )
+            }
+            Self::Project { input, columns } => {
+                let columns = columns.clone();
+                Box::new(input.execute_node(table, stats).map(move |mut row| {
+                    row.retain(|name, _| columns.contains(name));
+                    row
+                }))
+            }
+            Self::Sort {
+                input,
+                column,
+                order,
+            } => {
+                let mut input = input.execute_node(table, stats);
+                let column = column.clone();
+                let order = *order;
+                let mut sorted: Option<std::vec::IntoIter<Row<'a>>> = None;
+
+                // The rows are only sorted once the first one is requested.
+                Box::new(std::iter::from_fn(move || {
+                    sorted
+                        .get_or_insert_with(|| {
+                            let mut rows: Vec<Row<'a>> = input.by_ref().collect();
+                            rows.sort_by(|a, b| {
+                                let ordering = a.get(&column).cmp(&b.get(&column));
+                                match order {
+                                    SortOrder::Ascending => ordering,
+                                    SortOrder::Descending => ordering.reverse(),
+                                }
+                            });
+                            rows.into_iter()
+                        })
+                        .next()
+                }))
+            }
+            Self::Limit { input, count } => Box::new(input.execute_node(table, stats).take(*count)),
+        };
+
+        match node_stats {
+            Some(node_stats) => Box::new(Instrumented {
+                inner: rows,
+                stats: node_stats,
+                rows_scanned: None,
+            }),
+            None => rows,
+        }
+    }
+
+    /// Returns the description of the node alone, without its inputs.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Scan { columns, filters } if filters.is_empty() => {
+                format!("Scan [{}]", columns.join(", "))
+            }
+            Self::Scan { columns, filters } => format!(
+                "Scan [{}] filters: {}",
+                columns.join(", "),
+                format_filters(filters)
+            ),
+            Self::Empty { columns } => format!("Empty [{}]", columns.join(", ")),
+            Self::Filter { filters, .. } => format!("Filter {}", format_filters(filters)),
+            Self::Project { columns, .. } => format!("Project [{}]", columns.join(", ")),
+            Self::Sort { column, order, .. } => format!("Sort {column} {order}"),
+            Self::Limit { count, .. } => format!("Limit {count}"),
+        }
+    }
+
+    /// Returns an iterator over the node and its inputs, from the root
+    /// node down to the leaf.
+    pub fn nodes(&self) -> impl Iterator<Item = &LogicalPlan> {
+        std::iter::successors(Some(self), |node| node.input())
+    }
+}
+
+impl std::fmt::Display for LogicalPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (depth, node) in self.nodes().enumerate() {
+            if depth > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:indent$}{}", "", node.describe(), indent = depth * 2)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The statistics of a plan node collected while executing it.
+#[derive(Debug, Clone, Default)]
+pub struct NodeStats {
+    /// The number of rows produced by the node.
+    pub rows: usize,
+    /// The number of rows visited by the table, for a scan node.
+    pub rows_scanned: Option<usize>,
+    /// The time spent producing the rows, including the time spent by
+    /// the input nodes.
+    pub elapsed: Duration,
+}
+
+/// An iterator collecting the statistics of the rows produced by a
+/// plan node.
+struct Instrumented<I> {
+    inner: I,
+    stats: Rc<RefCell<NodeStats>>,
+    /// Returns the number of rows visited by the table so far, for a
+    /// scan node.
+    rows_scanned: Option<fn(&I) -> usize>,
+}
+
+impl<'a, I: Iterator<Item = Row<'a>>> Iterator for Instrumented<I> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Instant::now();
+        let row = self.inner.next();
+
+        let mut stats = self.stats.borrow_mut();
+        stats.elapsed += start.elapsed();
+        stats.rows += usize::from(row.is_some());
+        if let Some(rows_scanned) = self.rows_scanned {
+            stats.rows_scanned = Some(rows_scanned(&self.inner));
+        }
+
+        row
+    }
+}
+
+/// Formats the filters ordered by the column name.
+fn format_filters(filters: &HashMap<String, FilterByValue>) -> String {
+    let mut filters: Vec<_> = filters.iter().collect();
+    filters.sort_by_key(|(column, _)| *column);
+
+    filters
+        .into_iter()
+        .map(|(column, filter)| format!("{column} {} {}", filter.operation, filter.value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
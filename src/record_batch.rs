@@ -0,0 +1,277 @@
+//! The conversions between the column values and the Apache Arrow
+//! arrays, record batches and IPC files.
+
+use std::{
+    io::{Cursor, Read},
+    sync::Arc,
+};
+
+use arrow::{
+    array::{Array, ArrayRef, ArrowPrimitiveType, AsArray, Int64Array, StringArray},
+    datatypes::{
+        DataType, Field, Int16Type, Int32Type, Int64Type, Int8Type, Schema, UInt16Type, UInt32Type,
+        UInt8Type,
+    },
+    ipc::reader::FileReader,
+    record_batch::{RecordBatch, RecordBatchOptions},
+    util::display::{ArrayFormatter, FormatOptions},
+};
+
+use crate::{
+    compression,
+    csv_table::CsvTable,
+    error::{Error, Result},
+    table::{AsTable, ColumnType, ColumnValue, IntegerColumnType, StringColumnType},
+};
+
+/// Returns the Arrow field of the column: 64-bit integers for the integer
+/// columns and UTF-8 strings for the rest, all of them nullable.
+fn field(name: &str, column_type: Option<ColumnType>) -> Field {
+    let data_type = match column_type {
+        Some(ColumnType::Integer) => DataType::Int64,
+        _ => DataType::Utf8,
+    };
+
+    Field::new(name, data_type, true)
+}
+
+/// Converts the values of a column into an Arrow array of its field. The
+/// empty values become nulls.
+fn to_array<'a>(
+    column_type: Option<ColumnType>,
+    values: impl IntoIterator<Item = &'a ColumnValue>,
+) -> ArrayRef {
+    let values = values
+        .into_iter()
+        .map(|value| (!value.is_null()).then_some(value));
+
+    match column_type {
+        Some(ColumnType::Integer) => Arc::new(
+            values
+                .map(|value| value.and_then(ColumnValue::as_integer).map(|value| value.0))
+                .collect::<Int64Array>(),
+        ),
+        _ => Arc::new(
+            values
+                .map(|value| {
+                    value.map(|value| match value {
+                        ColumnValue::Integer(value) => value.to_string(),
+                        ColumnValue::String(value) => value.0.clone(),
+                    })
+                })
+                .collect::<StringArray>(),
+        ),
+    }
+}
+
+/// Builds the record batch of the columns, given their names, types and
+/// values, and the number of rows.
+pub fn to_record_batch<'a, I>(
+    columns: impl IntoIterator<Item = (&'a str, Option<ColumnType>, I)>,
+    row_count: usize,
+) -> Result<RecordBatch>
+where
+    I: IntoIterator<Item = &'a ColumnValue>,
+{
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = columns
+        .into_iter()
+        .map(|(name, column_type, values)| {
+            (field(name, column_type), to_array(column_type, values))
+        })
+        .unzip();
+    let options = RecordBatchOptions::new().with_row_count(Some(row_count));
+
+    Ok(RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(fields)),
+        arrays,
+        &options,
+    )?)
+}
+
+/// Converts the Arrow array into the column values.
+///
+/// The nulls become empty values. The integers of up to 64 bits, except
+/// for the unsigned 64-bit ones, and the strings are kept as such, while
+/// the other values become their text.
+pub fn from_array(array: &dyn Array) -> Result<Vec<ColumnValue>> {
+    let strings = |values: &mut dyn Iterator<Item = Option<&str>>| {
+        values
+            .map(|value| {
+                value.map_or_else(ColumnValue::null, |value| ColumnValue::String(value.into()))
+            })
+            .collect()
+    };
+
+    Ok(match array.data_type() {
+        DataType::Int8 => integers::<Int8Type>(array),
+        DataType::Int16 => integers::<Int16Type>(array),
+        DataType::Int32 => integers::<Int32Type>(array),
+        DataType::Int64 => integers::<Int64Type>(array),
+        DataType::UInt8 => integers::<UInt8Type>(array),
+        DataType::UInt16 => integers::<UInt16Type>(array),
+        DataType::UInt32 => integers::<UInt32Type>(array),
+        DataType::Utf8 => strings(&mut array.as_string::<i32>().iter()),
+        DataType::LargeUtf8 => strings(&mut array.as_string::<i64>().iter()),
+        DataType::Utf8View => strings(&mut array.as_string_view().iter()),
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())?;
+
+            (0..array.len())
+                .map(|i| {
+                    if array.is_null(i) {
+                        ColumnValue::null()
+                    } else {
+                        ColumnValue::String(StringColumnType(formatter.value(i).to_string()))
+                    }
+                })
+                .collect()
+        }
+    })
+}
+
+/// Reads the Arrow IPC file into the named columns, decompressing it if
+/// it is compressed with gzip, zstd or bzip2.
+pub fn read_arrow_ipc(path: &str) -> Result<Vec<(String, Vec<ColumnValue>)>> {
+    let mut data = Vec::new();
+    compression::open(path)?.read_to_end(&mut data)?;
+
+    let reader = FileReader::try_new(Cursor::new(data), None)?;
+    let schema = reader.schema();
+    let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+
+    columns(&schema, &batches)
+}
+
+/// Returns the named columns of the record batches of the schema,
+/// appended one after another.
+pub fn columns(
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<Vec<(String, Vec<ColumnValue>)>> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(column_idx, field)| {
+            let mut values = Vec::new();
+            for batch in batches {
+                values.extend(from_array(batch.column(column_idx))?);
+            }
+
+            Ok((field.name().clone(), values))
+        })
+        .collect()
+}
+
+/// Creates a table from the record batch.
+impl TryFrom<&RecordBatch> for CsvTable {
+    type Error = Error;
+
+    fn try_from(batch: &RecordBatch) -> Result<Self> {
+        Ok(CsvTable::from_columns(columns(
+            &batch.schema(),
+            std::slice::from_ref(batch),
+        )?))
+    }
+}
+
+/// Converts the table into a record batch.
+impl TryFrom<&CsvTable> for RecordBatch {
+    type Error = Error;
+
+    fn try_from(table: &CsvTable) -> Result<Self> {
+        let columns = table
+            .get_columns()
+            .map(|(name, column)| (name, table.get_column_type(name), column.iter()));
+
+        to_record_batch(columns, table.row_count())
+    }
+}
+
+/// Converts the Arrow array of integers into the column values.
+fn integers<T>(array: &dyn Array) -> Vec<ColumnValue>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Into<i64>,
+{
+    array
+        .as_primitive::<T>()
+        .iter()
+        .map(|value| {
+            value.map_or_else(ColumnValue::null, |value| {
+                ColumnValue::Integer(IntegerColumnType(value.into()))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{BooleanArray, Float64Array, Int32Array, UInt64Array};
+
+    use super::*;
+
+    #[test]
+    fn converts_arrays() {
+        let integer = |value| ColumnValue::Integer(IntegerColumnType(value));
+        let string = |value: &str| ColumnValue::String(StringColumnType(value.to_string()));
+
+        let array = Int32Array::from(vec![Some(1), None, Some(-3)]);
+        assert_eq!(
+            from_array(&array).unwrap(),
+            vec![integer(1), ColumnValue::null(), integer(-3)]
+        );
+
+        let array = StringArray::from(vec![Some("a"), None]);
+        assert_eq!(
+            from_array(&array).unwrap(),
+            vec![string("a"), ColumnValue::null()]
+        );
+
+        let array = BooleanArray::from(vec![Some(true), None]);
+        assert_eq!(
+            from_array(&array).unwrap(),
+            vec![string("true"), ColumnValue::null()]
+        );
+
+        let array = Float64Array::from(vec![0.5]);
+        assert_eq!(from_array(&array).unwrap(), vec![string("0.5")]);
+
+        let array = UInt64Array::from(vec![u64::MAX]);
+        assert_eq!(
+            from_array(&array).unwrap(),
+            vec![string("18446744073709551615")]
+        );
+
+        let values = [integer(1), string("x"), ColumnValue::null()];
+        let batch = to_record_batch([("mixed", None, values.iter())], values.len()).unwrap();
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Utf8);
+        assert_eq!(
+            from_array(batch.column(0)).unwrap(),
+            vec![string("1"), string("x"), ColumnValue::null()]
+        );
+    }
+
+    #[test]
+    fn table_round_trip() {
+        let data = b"id,name,mixed\n1,a,1\n2,,x\n";
+        let table = CsvTable::from_reader(data.as_slice(), &Default::default()).unwrap();
+
+        let batch = RecordBatch::try_from(&table).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(1).null_count(), 1);
+        assert_eq!(
+            CsvTable::try_from(&batch).unwrap().get_column_type("id"),
+            Some(ColumnType::Integer)
+        );
+
+        let loaded =
+            CsvTable::from_columns(columns(&batch.schema(), &[batch.clone(), batch]).unwrap());
+        assert_eq!(loaded.row_count(), 4);
+        assert_eq!(loaded.get_column_type("mixed"), Some(ColumnType::String));
+
+        let result = loaded.execute("PROJECT id, name FILTER id = 2").unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.value(0, "name"), Some(&ColumnValue::null()));
+    }
+}
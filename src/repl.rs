@@ -0,0 +1,202 @@
+//! The interactive read-eval-print loop.
+
+use std::{error::Error, path::PathBuf};
+
+use rustyline::{
+    completion::Completer,
+    error::ReadlineError,
+    history::DefaultHistory,
+    validate::{ValidationContext, ValidationResult, Validator},
+    Context, Editor, Helper, Highlighter, Hinter,
+};
+
+use prisma_test::{
+    cancel, completion,
+    session::{self, Session},
+};
+
+/// The name of the history file in the home directory.
+const HISTORY_FILE_NAME: &str = ".prisma_test_history";
+
+/// The prompt shown before every statement.
+const PROMPT: &str = "REPL > ";
+
+/// The line editor helper completing the statements and deciding when
+/// the input is complete.
+#[derive(Helper, Hinter, Highlighter)]
+struct ReplHelper {
+    /// The names of the columns to complete.
+    columns: Vec<String>,
+    /// The names of the tables to complete.
+    tables: Vec<String>,
+}
+
+impl ReplHelper {
+    /// Creates a helper completing the tables and the columns loaded in
+    /// the session.
+    fn new(session: &Session) -> Self {
+        Self {
+            columns: session.column_names(),
+            tables: session.table_names().map(str::to_owned).collect(),
+        }
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(completion::complete(line, pos, &self.columns, &self.tables))
+    }
+}
+
+impl Validator for ReplHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        Ok(if is_complete(ctx.input()) {
+            ValidationResult::Valid(None)
+        } else {
+            ValidationResult::Incomplete
+        })
+    }
+}
+
+/// Returns [`true`] if the input can be executed: the statements end with
+/// `;` outside of the quotes, which lets them span multiple lines, while
+/// the meta-commands take a single line.
+fn is_complete(input: &str) -> bool {
+    let (_, rest) = split_statements(input);
+    rest.trim().is_empty() || is_exit_command(input)
+}
+
+/// Returns [`true`] if the input is a command to leave.
+pub fn is_exit_command(input: &str) -> bool {
+    let input = input.trim().trim_end_matches(';').trim_end();
+    input.eq_ignore_ascii_case("exit") || input.eq_ignore_ascii_case("quit")
+}
+
+/// Splits the input into the statements ended by `;` outside of the
+/// quoted strings and paths, and returns them with the rest of the input,
+/// not ended yet. A meta-command takes the whole input.
+pub fn split_statements(input: &str) -> (Vec<&str>, &str) {
+    if session::is_command(input) {
+        return (vec![input.trim()], "");
+    }
+
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, ';') => {
+                statements.push(input[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.retain(|statement| !statement.is_empty());
+
+    (statements, &input[start..])
+}
+
+/// Splits the input into the statements, the last of which may not end
+/// with `;`.
+pub fn statements(input: &str) -> Vec<&str> {
+    let (mut statements, rest) = split_statements(input);
+    if !rest.trim().is_empty() {
+        statements.push(rest.trim());
+    }
+
+    statements
+}
+
+/// Returns the path to the history file, if the home directory is known.
+fn history_path() -> Option<PathBuf> {
+    std::env::home_dir().map(|home| home.join(HISTORY_FILE_NAME))
+}
+
+/// Runs the loop until the user leaves it.
+pub fn run(mut session: Session) -> Result<(), Box<dyn Error>> {
+    println!("Welcome to the CSV data query tool!");
+    println!("End the statements with ;, leave with exit, quit or CTRL-D.");
+    println!("Enter .help for the meta-commands.");
+
+    cancel::install_handler()?;
+
+    let mut editor = Editor::<ReplHelper, DefaultHistory>::new()?;
+    editor.set_helper(Some(ReplHelper::new(&session)));
+
+    let history_path = history_path();
+    if let Some(path) = &history_path {
+        // There is no history on the first run.
+        let _ = editor.load_history(path);
+    }
+
+    loop {
+        println!();
+
+        let input = match editor.readline(PROMPT) {
+            Ok(input) => input,
+            // CTRL-C discards the input being typed.
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        editor.add_history_entry(input.as_str())?;
+
+        if is_exit_command(&input) {
+            break;
+        }
+
+        println!();
+
+        for statement in statements(&input) {
+            if let Err(e) = session.execute(statement) {
+                eprintln!("Error occured: {e}");
+            }
+        }
+
+        // The meta-commands may load tables.
+        editor.set_helper(Some(ReplHelper::new(&session)));
+    }
+
+    if let Some(path) = &history_path {
+        editor.save_history(path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_statements() {
+        let (complete, rest) =
+            split_statements("PROJECT a FILTER b = \"x;y\" INTO 'a;b.csv'; PROJECT c\nFILTER");
+
+        assert_eq!(
+            complete,
+            vec!["PROJECT a FILTER b = \"x;y\" INTO 'a;b.csv'"]
+        );
+        assert_eq!(rest, " PROJECT c\nFILTER");
+        assert_eq!(
+            split_statements(".load 'a;b.csv'"),
+            (vec![".load 'a;b.csv'"], "")
+        );
+        assert_eq!(
+            statements("PROJECT a; PROJECT b"),
+            vec!["PROJECT a", "PROJECT b"]
+        );
+    }
+}
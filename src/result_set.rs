@@ -0,0 +1,247 @@
+//! The results of a query.
+
+use std::ops::Index;
+
+use arrow::record_batch::RecordBatch;
+
+use crate::{
+    cancel,
+    error::{Error, Result},
+    filter::{ApplyTableFilterByValue, FilterColumns},
+    plan::{LogicalPlan, Rows},
+    record_batch,
+    table::{AsTable, ColumnType, ColumnValue},
+};
+
+/// The rows returned by a query, owning their values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultSet {
+    /// The column names, in the order of the projection.
+    columns: Vec<String>,
+    /// The type of every column, if all its values in the table are of
+    /// the same type.
+    types: Vec<Option<ColumnType>>,
+    /// The values of every row, in the order of the columns.
+    rows: Vec<Vec<ColumnValue>>,
+}
+
+impl ResultSet {
+    /// Creates a result set from the rows of the values in the order of
+    /// the columns.
+    pub fn new(
+        columns: Vec<String>,
+        types: Vec<Option<ColumnType>>,
+        rows: Vec<Vec<ColumnValue>>,
+    ) -> Self {
+        Self {
+            columns,
+            types,
+            rows,
+        }
+    }
+
+    /// Collects the rows of the query as they are produced.
+    pub fn collect(rows: ResultStream<'_>) -> Result<Self> {
+        let columns = rows.columns.clone();
+        let types = rows.types.clone();
+
+        Ok(Self::new(columns, types, rows.collect::<Result<_>>()?))
+    }
+
+    /// Executes the optimized plan of the query against the table and
+    /// collects the rows.
+    pub fn execute<'a, T>(filter_columns: &FilterColumns, table: &'a T) -> Result<Self>
+    where
+        T: AsTable + ApplyTableFilterByValue<'a>,
+    {
+        Self::collect(ResultStream::execute(filter_columns, table)?)
+    }
+
+    /// Returns the column names, in the order of the projection.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the type of every column, [`None`] if the values are of
+    /// different types.
+    pub fn column_types(&self) -> &[Option<ColumnType>] {
+        &self.types
+    }
+
+    /// Returns the position of the column.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column == name)
+    }
+
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns [`true`] if there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the row with the given number.
+    pub fn get(&self, row_idx: usize) -> Option<&[ColumnValue]> {
+        self.rows.get(row_idx).map(Vec::as_slice)
+    }
+
+    /// Returns the value of the column in the row with the given number.
+    pub fn value(&self, row_idx: usize, column: &str) -> Option<&ColumnValue> {
+        self.get(row_idx)?.get(self.column_index(column)?)
+    }
+
+    /// Returns an iterator over the rows.
+    pub fn iter(&self) -> impl Iterator<Item = &[ColumnValue]> {
+        self.rows.iter().map(Vec::as_slice)
+    }
+
+    /// Converts the rows into an Arrow record batch, the integer columns
+    /// being 64-bit integers and the rest UTF-8 strings.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        let columns = self.columns.iter().zip(&self.types).enumerate().map(
+            |(column_idx, (name, column_type))| {
+                (
+                    name.as_str(),
+                    *column_type,
+                    self.rows.iter().map(move |row| &row[column_idx]),
+                )
+            },
+        );
+
+        record_batch::to_record_batch(columns, self.len())
+    }
+
+    /// Returns the rows, the values in the order of the columns.
+    pub fn into_rows(self) -> Vec<Vec<ColumnValue>> {
+        self.rows
+    }
+}
+
+/// Creates a result set from the Arrow record batch. The integers and
+/// strings are kept as such, while the other values become their text and
+/// the nulls are empty.
+impl TryFrom<&RecordBatch> for ResultSet {
+    type Error = Error;
+
+    fn try_from(batch: &RecordBatch) -> Result<Self> {
+        let columns = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        let values = batch
+            .columns()
+            .iter()
+            .map(|array| record_batch::from_array(array))
+            .collect::<Result<Vec<_>>>()?;
+        let types = values
+            .iter()
+            .map(|values| {
+                let mut types = values.iter().map(ColumnValue::get_type);
+                let first = types.next()?;
+                types
+                    .all(|column_type| column_type == first)
+                    .then_some(first)
+            })
+            .collect();
+        let rows = (0..batch.num_rows())
+            .map(|row_idx| {
+                values
+                    .iter()
+                    .map(|values| values[row_idx].clone())
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self::new(columns, types, rows))
+    }
+}
+
+impl Index<usize> for ResultSet {
+    type Output = [ColumnValue];
+
+    fn index(&self, row_idx: usize) -> &Self::Output {
+        &self.rows[row_idx]
+    }
+}
+
+/// The rows returned by a query, produced one at a time as the query
+/// runs.
+pub struct ResultStream<'a> {
+    /// The column names, in the order of the projection.
+    columns: Vec<String>,
+    /// The type of every column, if all its values in the table are of
+    /// the same type.
+    types: Vec<Option<ColumnType>>,
+    /// The rows of the plan.
+    rows: Rows<'a>,
+    /// Whether the last row was produced or the query was cancelled.
+    finished: bool,
+}
+
+impl<'a> ResultStream<'a> {
+    /// Executes the optimized plan of the query against the table.
+    pub fn execute<T>(filter_columns: &FilterColumns, table: &'a T) -> Result<Self>
+    where
+        T: AsTable + ApplyTableFilterByValue<'a>,
+    {
+        let plan = LogicalPlan::optimized(filter_columns, table)?;
+        let columns = filter_columns.output_columns.clone();
+        let types = columns
+            .iter()
+            .map(|column| table.get_column_type(column))
+            .collect();
+
+        Ok(Self {
+            columns,
+            types,
+            rows: plan.execute(table),
+            finished: false,
+        })
+    }
+
+    /// Returns the column names, in the order of the projection.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns the type of every column, [`None`] for a mixed-type column.
+    pub fn column_types(&self) -> &[Option<ColumnType>] {
+        &self.types
+    }
+}
+
+/// Yields the values of every row in the order of the columns, the
+/// missing ones being empty, or an error once the query is cancelled.
+impl Iterator for ResultStream<'_> {
+    type Item = Result<Vec<ColumnValue>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.rows.next() {
+            _ if cancel::is_cancelled() => {
+                self.finished = true;
+                Some(Err(Error::Cancelled))
+            }
+            Some(row) => Some(Ok(self
+                .columns
+                .iter()
+                .map(|column| {
+                    row.get(column)
+                        .map_or_else(ColumnValue::null, |value| (*value).clone())
+                })
+                .collect())),
+            None => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,715 @@
+//! The state of a session: the loaded tables and the settings changed by
+//! the meta-commands.
+
+use std::{
+    io::{self, Read},
+    path::Path,
+    time::Instant,
+};
+
+use crate::{
+    cancel, compression,
+    csv_options::CsvOptions,
+    csv_table::CsvTable,
+    error::{Error, Result},
+    explain::{self, Explanation},
+    filter::{self, FilterColumns, Statement},
+    fixed_width::FixedWidthLayout,
+    json_table::JsonTable,
+    output::{self, OutputMode},
+    parquet_table::ParquetTable,
+    record_batch,
+    result_set::{ResultSet, ResultStream},
+    sniffer,
+    spreadsheet::{self, Sheet},
+    sqlite_table::SqliteTable,
+    table::{self, AsTable, ColumnType, LoadOnDemand},
+};
+
+/// The meta-commands and their arguments.
+pub const COMMANDS: [(&str, &str); 6] = [
+    (".help", "Show the available commands"),
+    (
+        ".load",
+        "Load a CSV, JSON, Parquet, Arrow, SQLite or spreadsheet file as a table: .load PATH [TABLE SOURCE] [LAYOUT FILE] [AS NAME]",
+    ),
+    (".mode", "Show or set the output mode: .mode [MODE]"),
+    (".schema", "Show the columns of the tables: .schema [TABLE]"),
+    (".tables", "List the loaded tables"),
+    (
+        ".timer",
+        "Show or set timing of the statements: .timer [on|off]",
+    ),
+];
+
+/// The options of a single file loaded as a table.
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// The table of the SQLite database to load, its only one if
+    /// [`None`].
+    pub table: Option<String>,
+    /// The layout of the file, read as fixed-width whatever its
+    /// extension if given.
+    pub layout: Option<FixedWidthLayout>,
+}
+
+/// The loaded tables and the settings of a session.
+#[derive(Debug, Default)]
+pub struct Session {
+    /// The loaded tables and their names, in the order they were loaded.
+    /// The first one is queried when a statement names no table.
+    tables: Vec<(String, Box<dyn LoadedTable>)>,
+    /// Whether the time spent executing every statement is printed.
+    timer: bool,
+    /// The format the query results are printed in.
+    mode: OutputMode,
+    /// The dialect of the loaded CSV files, guessed for every file if
+    /// [`None`].
+    csv_options: Option<CsvOptions>,
+    /// The sheet of the loaded spreadsheets.
+    sheet: Sheet,
+}
+
+/// A table loaded in a session, of any of the supported formats.
+trait LoadedTable: std::fmt::Debug {
+    /// Returns the format of the file the table was loaded from.
+    fn format(&self) -> String;
+
+    /// Returns the number of rows in the table.
+    fn row_count(&self) -> usize;
+
+    /// Returns the names of the columns and their types, [`None`] for a
+    /// mixed-type column.
+    fn columns(&self) -> Vec<(&String, Option<ColumnType>)>;
+
+    /// Returns [`true`] if the column is indexed.
+    fn has_index(&self, _column: &str) -> bool {
+        false
+    }
+
+    /// Builds an index on the column. Only the tables held in memory are
+    /// indexed.
+    fn create_index(&mut self, _column: &str) -> Result {
+        Err(Error::InvalidCommand(
+            "Only the tables loaded into memory can be indexed".to_owned(),
+        ))
+    }
+
+    /// Queries the table and hands the rows to `consume` as they are
+    /// produced.
+    fn stream(
+        &self,
+        filter: &FilterColumns,
+        consume: &mut dyn FnMut(ResultStream<'_>) -> Result,
+    ) -> Result;
+
+    /// Describes how the query is executed against the table.
+    fn explain(&self, filter: &FilterColumns, analyze: bool) -> Result<Explanation>;
+}
+
+/// A table held in memory, loaded from a CSV, JSON, Arrow IPC,
+/// fixed-width or spreadsheet file.
+#[derive(Debug)]
+struct MemoryTable {
+    /// The values of the table.
+    table: CsvTable,
+    /// The format of the file the table was loaded from.
+    format: String,
+}
+
+impl MemoryTable {
+    /// Wraps the table loaded from a file of the format.
+    fn new(table: CsvTable, format: impl Into<String>) -> Box<Self> {
+        Box::new(Self {
+            table,
+            format: format.into(),
+        })
+    }
+
+    /// Wraps the table loaded from a JSON file.
+    fn json(json: JsonTable) -> Box<Self> {
+        let format = json.format();
+
+        Self::new(json.table, format)
+    }
+
+    /// Wraps the table loaded from a CSV file, described by its dialect.
+    fn csv(table: CsvTable) -> Box<Self> {
+        let format = table.dialect().to_string();
+
+        Self::new(table, format)
+    }
+}
+
+impl LoadedTable for MemoryTable {
+    fn format(&self) -> String {
+        self.format.clone()
+    }
+
+    fn row_count(&self) -> usize {
+        self.table.row_count()
+    }
+
+    fn columns(&self) -> Vec<(&String, Option<ColumnType>)> {
+        columns(&self.table)
+    }
+
+    fn has_index(&self, column: &str) -> bool {
+        self.table.has_index(column)
+    }
+
+    fn create_index(&mut self, column: &str) -> Result {
+        self.table.create_index(column)
+    }
+
+    fn stream(
+        &self,
+        filter: &FilterColumns,
+        consume: &mut dyn FnMut(ResultStream<'_>) -> Result,
+    ) -> Result {
+        consume(ResultStream::execute(filter, &self.table)?)
+    }
+
+    fn explain(&self, filter: &FilterColumns, analyze: bool) -> Result<Explanation> {
+        explain::explain(filter, &self.table, analyze)
+    }
+}
+
+impl LoadedTable for ParquetTable {
+    fn format(&self) -> String {
+        "Parquet".to_string()
+    }
+
+    fn row_count(&self) -> usize {
+        self.row_count()
+    }
+
+    fn columns(&self) -> Vec<(&String, Option<ColumnType>)> {
+        columns(self)
+    }
+
+    fn stream(
+        &self,
+        filter: &FilterColumns,
+        consume: &mut dyn FnMut(ResultStream<'_>) -> Result,
+    ) -> Result {
+        consume(ResultStream::execute(filter, &self.load(filter)?)?)
+    }
+
+    /// Loads the values first, as the statistics of the row groups are
+    /// only consulted then.
+    fn explain(&self, filter: &FilterColumns, analyze: bool) -> Result<Explanation> {
+        explain::explain(filter, &self.load(filter)?, analyze)
+    }
+}
+
+impl LoadedTable for SqliteTable {
+    fn format(&self) -> String {
+        "SQLite".to_string()
+    }
+
+    fn row_count(&self) -> usize {
+        self.row_count()
+    }
+
+    fn columns(&self) -> Vec<(&String, Option<ColumnType>)> {
+        columns(self)
+    }
+
+    fn stream(
+        &self,
+        filter: &FilterColumns,
+        consume: &mut dyn FnMut(ResultStream<'_>) -> Result,
+    ) -> Result {
+        consume(ResultStream::execute(filter, &self.load(filter)?)?)
+    }
+
+    fn explain(&self, filter: &FilterColumns, analyze: bool) -> Result<Explanation> {
+        explain::explain(filter, &self.load(filter)?, analyze)
+    }
+}
+
+/// Returns the names of the columns of the table and their types.
+fn columns(table: &impl AsTable) -> Vec<(&String, Option<ColumnType>)> {
+    table
+        .get_column_names()
+        .map(|column| (column, table.get_column_type(column)))
+        .collect()
+}
+
+/// Returns the lowercase extension of the file, ignoring the one of the
+/// compression.
+fn extension(path: &str) -> String {
+    Path::new(compression::strip_extension(path))
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// Returns [`true`] if the input is a meta-command rather than a
+/// statement.
+pub fn is_command(input: &str) -> bool {
+    input.trim_start().starts_with('.')
+}
+
+/// The file name standing for the standard input.
+pub const STDIN: &str = "-";
+
+/// Returns the default name of the table loaded from the file: its name
+/// without the extension, with the characters not allowed in the table
+/// names replaced by `_`. The table loaded from the standard input is
+/// named `stdin`.
+pub fn table_name(path: &str) -> String {
+    if path == STDIN {
+        return "stdin".to_string();
+    }
+
+    table::identifier(
+        &Path::new(compression::strip_extension(path))
+            .file_stem()
+            .map(|stem| stem.to_string_lossy())
+            .unwrap_or_default(),
+    )
+}
+
+impl Session {
+    /// Loads the CSV, fixed-width, JSON, Parquet or Arrow IPC file, the
+    /// sheet of the spreadsheet or the table of the SQLite database with
+    /// the name as a table, replacing the loaded table with the same name,
+    /// if any. The name defaults to the one of the SQLite table, if
+    /// given, or of the file. The file named [`STDIN`] is read from the
+    /// standard input to its end, and holds JSON if it starts with `{` or
+    /// `[`.
+    pub fn load(&mut self, path: &str, name: Option<&str>, options: &LoadOptions) -> Result {
+        let name = match (name, &options.table) {
+            (Some(name), _) => name.to_owned(),
+            (None, Some(table)) => table::identifier(table),
+            (None, None) => table_name(path),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(Error::InvalidCommand(format!("Invalid table name: {name}")));
+        }
+
+        let table: Box<dyn LoadedTable> = if let Some(layout) = &options.layout {
+            let reader = if path == STDIN {
+                compression::decompress(io::stdin().lock())?
+            } else {
+                compression::open(path)?
+            };
+
+            MemoryTable::new(CsvTable::from_columns(layout.read(reader)?), "Fixed-width")
+        } else if path == STDIN {
+            let mut data = Vec::new();
+            compression::decompress(io::stdin().lock())?.read_to_end(&mut data)?;
+
+            if data.trim_ascii_start().starts_with(b"{")
+                || data.trim_ascii_start().starts_with(b"[")
+            {
+                MemoryTable::json(JsonTable::from_reader(data.as_slice())?)
+            } else {
+                let options = self
+                    .csv_options
+                    .unwrap_or_else(|| sniffer::sniff_start(&data));
+                MemoryTable::csv(CsvTable::from_reader(data.as_slice(), &options)?)
+            }
+        } else {
+            match extension(path).as_str() {
+                "json" | "jsonl" | "ndjson" => MemoryTable::json(JsonTable::from_json(path)?),
+                "parquet" => Box::new(ParquetTable::from_parquet(path)?),
+                "arrow" | "ipc" | "feather" => MemoryTable::new(
+                    CsvTable::from_columns(record_batch::read_arrow_ipc(path)?),
+                    "Arrow IPC",
+                ),
+                "xlsx" | "xlsm" | "xlsb" | "xls" | "ods" => MemoryTable::new(
+                    CsvTable::from_columns(spreadsheet::read_sheet(path, &self.sheet)?),
+                    "Spreadsheet",
+                ),
+                "db" | "sqlite" | "sqlite3" => {
+                    Box::new(SqliteTable::from_sqlite(path, options.table.as_deref())?)
+                }
+                _ => {
+                    let options = match self.csv_options {
+                        Some(options) => options,
+                        None => sniffer::sniff_file(path)?,
+                    };
+                    MemoryTable::csv(CsvTable::from_csv_with_options(path, &options)?)
+                }
+            }
+        };
+
+        match self.tables.iter_mut().find(|(loaded, _)| *loaded == name) {
+            Some((_, loaded)) => *loaded = table,
+            None => self.tables.push((name, table)),
+        }
+
+        Ok(())
+    }
+
+    /// Sets the format the query results are printed in.
+    pub fn set_mode(&mut self, mode: OutputMode) {
+        self.mode = mode;
+    }
+
+    /// Sets the dialect of the CSV files loaded from now on, [`None`] to
+    /// guess it for every file.
+    pub fn set_csv_options(&mut self, csv_options: Option<CsvOptions>) {
+        self.csv_options = csv_options;
+    }
+
+    /// Sets the sheet of the spreadsheets loaded from now on.
+    pub fn set_sheet(&mut self, sheet: Sheet) {
+        self.sheet = sheet;
+    }
+
+    /// Returns the names of the loaded tables.
+    pub fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.tables.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the names of the columns of all the loaded tables.
+    pub fn column_names(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+
+        for (_, table) in &self.tables {
+            for (column, _) in table.columns() {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+        }
+
+        columns
+    }
+
+    /// Returns the table with the name, or the default one.
+    fn table(&self, name: Option<&str>) -> Result<&dyn LoadedTable> {
+        self.table_index(name).map(|i| self.tables[i].1.as_ref())
+    }
+
+    /// Returns the mutable table with the name, or the default one.
+    fn table_mut(&mut self, name: Option<&str>) -> Result<&mut Box<dyn LoadedTable>> {
+        self.table_index(name).map(|i| &mut self.tables[i].1)
+    }
+
+    /// Returns the position of the table with the name, or of the default
+    /// one.
+    fn table_index(&self, name: Option<&str>) -> Result<usize> {
+        match name {
+            Some(name) => self
+                .tables
+                .iter()
+                .position(|(loaded, _)| loaded == name)
+                .ok_or_else(|| Error::TableNotFound(name.to_owned())),
+            None if self.tables.is_empty() => {
+                Err(Error::TableNotFound("no table is loaded".to_owned()))
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Executes a single statement or meta-command.
+    pub fn execute(&mut self, input: &str) -> Result {
+        if is_command(input) {
+            return self.execute_command(input);
+        }
+
+        let start = Instant::now();
+        let result = self.execute_statement(input);
+
+        if self.timer {
+            println!("Run time: {}", explain::format_duration(start.elapsed()));
+        }
+
+        result
+    }
+
+    /// Parses and executes a single statement.
+    fn execute_statement(&mut self, input: &str) -> Result {
+        let _running = cancel::start();
+
+        match filter::parse_statement(input)? {
+            Statement::Query(mut filter) => {
+                let into = filter.into.take();
+                let format = filter.format;
+                let table = self.table(filter.table.as_deref())?;
+
+                match into {
+                    // The files are meant for the other tools rather than
+                    // for reading, so they are written in the format named
+                    // by their extension, or in CSV.
+                    Some(path) => {
+                        let format = format
+                            .or_else(|| OutputMode::from_path(&path))
+                            .unwrap_or(OutputMode::Csv);
+                        let mut written = 0;
+                        table.stream(&filter, &mut |rows| {
+                            let result = ResultSet::collect(rows)?;
+                            written = result.len();
+                            output::write_file(&path, format, &result)
+                        })?;
+                        println!("Wrote {written} rows to {path}");
+                        Ok(())
+                    }
+                    None => {
+                        let mode = format.unwrap_or(self.mode);
+                        table.stream(&filter, &mut |rows| output::print(mode, rows))
+                    }
+                }
+            }
+            Statement::Explain(filter) => {
+                let table = self.table(filter.table.as_deref())?;
+                table
+                    .explain(&filter, false)
+                    .map(|explanation| println!("{explanation}"))
+            }
+            Statement::ExplainAnalyze(filter) => {
+                let table = self.table(filter.table.as_deref())?;
+                table
+                    .explain(&filter, true)
+                    .map(|explanation| println!("{explanation}"))
+            }
+            Statement::CreateIndex { table, column } => {
+                self.table_mut(table.as_deref())?.create_index(&column)
+            }
+        }
+    }
+
+    /// Parses and executes a single meta-command.
+    fn execute_command(&mut self, input: &str) -> Result {
+        let input = input.trim().trim_end_matches(';');
+        let arguments = split_arguments(input)?;
+        let (command, arguments) = match arguments.split_first() {
+            Some((command, arguments)) => (*command, arguments),
+            None => ("", &[][..]),
+        };
+
+        match (command, arguments) {
+            (".tables", []) => {
+                for (name, table) in &self.tables {
+                    println!("{name} ({} rows)", table.row_count());
+                }
+            }
+            (".schema", []) => {
+                for (name, table) in &self.tables {
+                    print_schema(name, table.as_ref());
+                }
+            }
+            (".schema", [name]) => print_schema(name, self.table(Some(name))?),
+            (".load", [path, options @ ..]) => {
+                let (name, options) = parse_load_options(options)?;
+                self.load(path, name, &options)?
+            }
+            (".timer", []) => println!("Timer: {}", if self.timer { "on" } else { "off" }),
+            (".timer", [setting]) => {
+                self.timer = match setting.to_ascii_lowercase().as_str() {
+                    "on" => true,
+                    "off" => false,
+                    _ => {
+                        return Err(Error::InvalidCommand(format!(
+                            "Expected on or off: {setting}"
+                        )))
+                    }
+                }
+            }
+            (".mode", []) => {
+                let modes: Vec<_> = OutputMode::ALL
+                    .iter()
+                    .filter(|mode| !mode.is_binary())
+                    .map(OutputMode::as_str)
+                    .collect();
+                println!(
+                    "Output mode: {} (available: {})",
+                    self.mode,
+                    modes.join(", ")
+                );
+            }
+            (".mode", [mode]) => {
+                self.mode = match mode.parse::<OutputMode>()? {
+                    mode if mode.is_binary() => return Err(output::binary_error(mode)),
+                    mode => mode,
+                }
+            }
+            (".help", []) => {
+                for (name, usage) in COMMANDS {
+                    println!("{name:<8} {usage}");
+                }
+            }
+            _ => {
+                let commands: Vec<_> = COMMANDS.iter().map(|(name, _)| *name).collect();
+
+                return Err(Error::InvalidCommand(
+                    match COMMANDS.iter().find(|(name, _)| *name == command) {
+                        Some((_, usage)) => format!("{input}. {usage}"),
+                        None => format!(
+                            "{command}. The available commands are: {}",
+                            commands.join(", ")
+                        ),
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits the meta-command into its arguments separated by whitespace,
+/// the ones in single or double quotes, such as the paths with spaces,
+/// taken whole.
+fn split_arguments(input: &str) -> Result<Vec<&str>> {
+    let mut arguments = Vec::new();
+    let mut rest = input.trim_start();
+
+    while let Some(first) = rest.chars().next() {
+        let (argument, next) = match first {
+            '\'' | '"' => {
+                let end = rest[1..]
+                    .find(first)
+                    .ok_or_else(|| Error::InvalidCommand(format!("Unclosed quote in {input}")))?
+                    + 1;
+                (&rest[1..end], &rest[end + 1..])
+            }
+            _ => rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len())),
+        };
+        arguments.push(argument);
+        rest = next.trim_start();
+    }
+
+    Ok(arguments)
+}
+
+/// Parses the arguments of `.load` following the path: the optional
+/// `TABLE SOURCE`, `LAYOUT FILE` and `AS NAME`, in any order.
+fn parse_load_options<'a>(arguments: &[&'a str]) -> Result<(Option<&'a str>, LoadOptions)> {
+    let mut name = None;
+    let mut options = LoadOptions::default();
+
+    for pair in arguments.chunks(2) {
+        match pair {
+            [keyword, value] if keyword.eq_ignore_ascii_case("AS") && name.is_none() => {
+                name = Some(*value)
+            }
+            [keyword, value]
+                if keyword.eq_ignore_ascii_case("TABLE") && options.table.is_none() =>
+            {
+                options.table = Some(value.to_string())
+            }
+            [keyword, value]
+                if keyword.eq_ignore_ascii_case("LAYOUT") && options.layout.is_none() =>
+            {
+                options.layout = Some(FixedWidthLayout::from_file(value)?)
+            }
+            _ => {
+                return Err(Error::InvalidCommand(format!(
+                    "Unexpected {} after the path",
+                    pair.join(" ")
+                )))
+            }
+        }
+    }
+
+    Ok((name, options))
+}
+
+/// Prints the format of the table, its columns and their types.
+fn print_schema(name: &str, table: &dyn LoadedTable) {
+    println!("{name} ({}):", table.format());
+
+    for (column, column_type) in table.columns() {
+        let column_type = column_type.map_or("mixed", |column_type| column_type.as_str());
+        let index = if table.has_index(column) {
+            ", indexed"
+        } else {
+            ""
+        };
+
+        println!("  {column}: {column_type}{index}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// Returns the path of the file of the name in a directory of the
+    /// test, after writing the contents to it.
+    fn write_file(test: &str, name: &str, contents: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("prisma-test-{}-{test}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+
+        path.to_str().unwrap().to_string()
+    }
+
+    /// Returns the names and the formats of the loaded tables.
+    fn tables(session: &Session) -> Vec<(&str, String)> {
+        session
+            .tables
+            .iter()
+            .map(|(name, table)| (name.as_str(), table.format()))
+            .collect()
+    }
+
+    #[test]
+    fn loads_tables() {
+        let csv = write_file("load", "sales 2024.csv", "id,name\n1,a\n");
+        let json = write_file("load", "events.json", "[{\"id\": 1}]");
+        let ndjson = write_file("load", "logs.ndjson", "{\"id\": 1}\n{\"id\": 2}\n");
+        let mut session = Session::default();
+
+        session.load(&csv, None, &LoadOptions::default()).unwrap();
+        session.load(&json, None, &LoadOptions::default()).unwrap();
+        assert_eq!(session.table(Some("events")).unwrap().format(), "JSON");
+        session
+            .load(&ndjson, Some("events"), &LoadOptions::default())
+            .unwrap();
+
+        let dialect = CsvOptions::default().to_string();
+        assert_eq!(
+            tables(&session),
+            vec![("sales_2024", dialect), ("events", "NDJSON".to_string())]
+        );
+        assert_eq!(session.table(Some("events")).unwrap().row_count(), 2);
+        assert!(matches!(
+            session.load(&csv, Some("sales-2024"), &LoadOptions::default()),
+            Err(Error::InvalidCommand(message)) if message == "Invalid table name: sales-2024"
+        ));
+
+        fs::remove_dir_all(Path::new(&csv).parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn executes_commands() {
+        let path = write_file("commands", "cities of 2025.csv", "id,name\n1,a\n2,b\n");
+        let mut session = Session::default();
+
+        session
+            .execute_command(&format!(".load '{path}' AS cities;"))
+            .unwrap();
+        session.execute_command(".mode json").unwrap();
+        session.execute_command(".timer on").unwrap();
+
+        assert_eq!(session.table_names().collect::<Vec<_>>(), vec!["cities"]);
+        assert_eq!(session.mode, OutputMode::Json);
+        assert!(session.timer);
+        assert!(session.execute_command(".mode parquet").is_err());
+        assert!(session.execute_command(".timer maybe").is_err());
+        assert!(session.execute_command(".load data.csv AS").is_err());
+        assert!(session.execute_command(".load 'data.csv").is_err());
+        assert!(session.execute_command(".unknown").is_err());
+
+        fs::remove_dir_all(Path::new(&path).parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn splits_arguments() {
+        assert_eq!(
+            split_arguments(r#".load 'my file.csv' AS  t "x y""#).unwrap(),
+            vec![".load", "my file.csv", "AS", "t", "x y"]
+        );
+        assert!(split_arguments(".load \"my file.csv").is_err());
+    }
+}
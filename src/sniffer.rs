@@ -0,0 +1,219 @@
+//! The guessing of the dialect of the CSV files.
+
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+};
+
+use csv::StringRecord;
+
+use crate::{compression, csv_options::CsvOptions, table::ColumnType};
+
+/// The number of bytes inspected at the beginning of a file.
+pub const SAMPLE_SIZE: usize = 16 * 1024;
+
+/// The characters which may separate the fields, by preference.
+const DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// Guesses the dialect of the CSV file from its beginning.
+pub fn sniff_file(path: &str) -> io::Result<CsvOptions> {
+    let mut sample = Vec::with_capacity(SAMPLE_SIZE);
+    compression::open(path)?
+        .take(SAMPLE_SIZE as u64)
+        .read_to_end(&mut sample)?;
+
+    Ok(sniff_start(&sample))
+}
+
+/// Guesses the dialect of the CSV data from its first [`SAMPLE_SIZE`]
+/// bytes.
+pub fn sniff_start(data: &[u8]) -> CsvOptions {
+    let mut sample = &data[..data.len().min(SAMPLE_SIZE)];
+
+    // The last line is likely cut short.
+    if sample.len() == SAMPLE_SIZE {
+        if let Some(end) = sample.iter().rposition(|&byte| byte == b'\n') {
+            sample = &sample[..=end];
+        }
+    }
+
+    sniff(sample)
+}
+
+/// Guesses the dialect of the CSV data: the delimiter, the quote, whether
+/// the fields are padded with whitespace and whether there is a header.
+pub fn sniff(sample: &[u8]) -> CsvOptions {
+    let quote = sniff_quote(sample);
+
+    let mut delimiter = DELIMITERS[0];
+    let mut best_score = (0, 0);
+    for candidate in DELIMITERS {
+        let score = score(sample, candidate, quote);
+        if score > best_score {
+            delimiter = candidate;
+            best_score = score;
+        }
+    }
+
+    let options = CsvOptions::new().delimiter(delimiter).quote(quote);
+    let records = read_records(sample, &options);
+
+    let fields: Vec<&str> = records
+        .iter()
+        .flat_map(StringRecord::iter)
+        .filter(|field| !field.is_empty())
+        .collect();
+    let padded = fields
+        .iter()
+        .filter(|field| field.trim() != **field)
+        .count();
+    let trim = padded * 2 > fields.len();
+
+    let records = read_records(sample, &options.trim(trim));
+
+    options.trim(trim).has_headers(has_header(&records))
+}
+
+/// Returns the quote of the fields: `'` if more fields start with it than
+/// with `"`.
+fn sniff_quote(sample: &[u8]) -> u8 {
+    let quoted_fields = |quote: u8| {
+        sample
+            .iter()
+            .enumerate()
+            .filter(|&(i, &byte)| {
+                byte == quote
+                    && (i == 0
+                        || matches!(sample[i - 1], b'\n' | b'\r' | b' ')
+                        || DELIMITERS.contains(&sample[i - 1]))
+            })
+            .count()
+    };
+
+    if quoted_fields(b'\'') > quoted_fields(b'"') {
+        b'\''
+    } else {
+        b'"'
+    }
+}
+
+/// Returns how well the delimiter splits the sample: the number of the
+/// records with the most common number of fields, and that number. A
+/// delimiter splitting no record scores nothing.
+fn score(sample: &[u8], delimiter: u8, quote: u8) -> (usize, usize) {
+    let options = CsvOptions::new().delimiter(delimiter).quote(quote);
+
+    let mut field_counts: HashMap<usize, usize> = HashMap::new();
+    for record in read_records(sample, &options) {
+        *field_counts.entry(record.len()).or_default() += 1;
+    }
+
+    field_counts
+        .into_iter()
+        .filter(|&(fields, _)| fields > 1)
+        .map(|(fields, records)| (records, fields))
+        .max()
+        .unwrap_or_default()
+}
+
+/// Reads the records of the sample, skipping the malformed ones.
+fn read_records(sample: &[u8], options: &CsvOptions) -> Vec<StringRecord> {
+    options
+        .reader_builder()
+        .flexible(true)
+        .from_reader(sample)
+        .into_records()
+        .filter_map(Result::ok)
+        .collect()
+}
+
+/// Returns the type of every column of the records, [`None`] if there are
+/// no values or they are of different types.
+fn column_types(records: &[StringRecord]) -> Vec<Option<ColumnType>> {
+    let columns = records.iter().map(StringRecord::len).max().unwrap_or(0);
+
+    (0..columns)
+        .map(|i| {
+            let mut values = records
+                .iter()
+                .filter_map(|record| record.get(i))
+                .filter(|value| !value.is_empty())
+                .peekable();
+
+            values.peek()?;
+
+            if values.all(|value| value.parse::<i64>().is_ok()) {
+                Some(ColumnType::Integer)
+            } else {
+                Some(ColumnType::String)
+            }
+        })
+        .collect()
+}
+
+/// Returns [`true`] if the first record looks like the column names: a
+/// column of integers has no integer in it, or a column of strings of the
+/// same length has a string of another length in it.
+fn has_header(records: &[StringRecord]) -> bool {
+    let Some((first, rest)) = records.split_first() else {
+        return true;
+    };
+    if rest.is_empty() {
+        return true;
+    }
+
+    let mut votes = 0;
+    for (i, (name, column_type)) in first.iter().zip(column_types(rest)).enumerate() {
+        match column_type {
+            Some(ColumnType::Integer) => {
+                votes += if name.parse::<i64>().is_ok() { -1 } else { 1 };
+            }
+            Some(ColumnType::String) => {
+                let mut lengths = rest.iter().filter_map(|record| record.get(i)).map(str::len);
+                let length = lengths.next().unwrap_or_default();
+
+                if lengths.all(|other| other == length) {
+                    votes += if name.len() == length { -1 } else { 1 };
+                }
+            }
+            None => {}
+        }
+    }
+
+    votes >= 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_delimiter_and_header() {
+        let options = sniff(b"id;name;amount\n1;a, b;10\n2;c;20\n");
+        assert_eq!(options, CsvOptions::new().delimiter(b';').has_headers(true));
+
+        let options = sniff(b"1\tx\t10\n2\ty\t20\n3\tz\t30\n");
+        assert_eq!(
+            options,
+            CsvOptions::new().delimiter(b'\t').has_headers(false)
+        );
+    }
+
+    #[test]
+    fn sniffs_quote_and_trimming() {
+        let options = sniff(b"id|name\n1|'a|b'\n2|'c'\n");
+        assert_eq!(options, CsvOptions::new().delimiter(b'|').quote(b'\''));
+
+        let options = sniff(b"a, b, c\n1, 2, 3\n4, 5, 6\n");
+        assert_eq!(options, CsvOptions::new().trim(true));
+    }
+
+    #[test]
+    fn guesses_column_types() {
+        let records = read_records(b"1,a,\n2,3,\n", &CsvOptions::new());
+        assert_eq!(
+            column_types(&records),
+            vec![Some(ColumnType::Integer), Some(ColumnType::String), None]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! The sheets of the spreadsheet files: Excel workbooks and OpenDocument
+//! spreadsheets.
+
+use std::{convert::Infallible, str::FromStr};
+
+use calamine::{open_workbook_auto, Data, ExcelDateTime, Range, Reader};
+
+use crate::{
+    error::{Error, Result},
+    table::{self, ColumnValue, IntegerColumnType, StringColumnType},
+};
+
+/// The sheet of a workbook to load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sheet {
+    /// The sheet with the name.
+    Name(String),
+    /// The sheet at the position, starting from 1.
+    Index(usize),
+}
+
+impl Default for Sheet {
+    /// The first sheet.
+    fn default() -> Self {
+        Sheet::Index(1)
+    }
+}
+
+/// Parses a positive number as the position of the sheet, and anything
+/// else as its name.
+impl FromStr for Sheet {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s.parse() {
+            Ok(index) if index > 0 => Sheet::Index(index),
+            _ => Sheet::Name(s.to_owned()),
+        })
+    }
+}
+
+impl std::fmt::Display for Sheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sheet::Name(name) => write!(f, "{name}"),
+            Sheet::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+/// Reads the sheet of the workbook into the named columns. A sheet named
+/// after the position takes precedence over the one at the position.
+pub fn read_sheet(path: &str, sheet: &Sheet) -> Result<Vec<(String, Vec<ColumnValue>)>> {
+    let mut workbook = open_workbook_auto(path)?;
+    let name = sheet.to_string();
+
+    let range = match sheet {
+        _ if workbook.sheet_names().contains(&name) => workbook.worksheet_range(&name)?,
+        Sheet::Index(index) => match workbook.worksheet_range_at(index - 1) {
+            Some(range) => range?,
+            None => return Err(Error::TableNotFound(format!("sheet {sheet}"))),
+        },
+        Sheet::Name(_) => return Err(Error::TableNotFound(format!("sheet {sheet}"))),
+    };
+
+    Ok(columns(&range))
+}
+
+/// Returns the named columns of the cells. The first row holds the column
+/// names, with the characters not allowed in the statements replaced by
+/// `_` and the blank ones named `c1`, `c2` and so on after their position.
+/// The rows of the empty cells only are skipped.
+fn columns(range: &Range<Data>) -> Vec<(String, Vec<ColumnValue>)> {
+    let mut rows = range.rows();
+    let Some(header) = rows.next() else {
+        return Vec::new();
+    };
+
+    let mut columns: Vec<(String, Vec<ColumnValue>)> = header
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| match cell.to_string().trim() {
+            "" => (format!("c{}", i + 1), Vec::new()),
+            name => (table::identifier(name), Vec::new()),
+        })
+        .collect();
+
+    for row in rows.filter(|row| row.iter().any(|cell| *cell != Data::Empty)) {
+        for ((_, values), cell) in columns.iter_mut().zip(row) {
+            values.push(to_column_value(cell));
+        }
+    }
+
+    columns
+}
+
+/// Converts the cell into a column value.
+///
+/// The whole numbers are integers, while the other numbers, the booleans
+/// and the errors become their text. The dates and the times are written
+/// in ISO 8601, the empty cells are nulls.
+fn to_column_value(cell: &Data) -> ColumnValue {
+    let string = |value: String| ColumnValue::String(StringColumnType(value));
+
+    match cell {
+        Data::Int(value) => ColumnValue::Integer(IntegerColumnType(*value)),
+        // The numbers are stored as floating point ones, integers
+        // included.
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 2f64.powi(63) => {
+            ColumnValue::Integer(IntegerColumnType(*value as i64))
+        }
+        Data::Float(value) => string(value.to_string()),
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => {
+            string(value.clone())
+        }
+        Data::Bool(value) => string(value.to_string()),
+        Data::DateTime(value) => string(format_date_time(value)),
+        Data::Error(error) => string(error.to_string()),
+        Data::Empty => ColumnValue::null(),
+    }
+}
+
+/// Returns the date and time in ISO 8601: the date only at midnight, the
+/// time only before the first day, and the hours, the minutes and the
+/// seconds of a duration.
+fn format_date_time(value: &ExcelDateTime) -> String {
+    if value.is_duration() {
+        let seconds = (value.as_f64() * 86_400.0).round() as i64;
+        let sign = if seconds < 0 { "-" } else { "" };
+        let seconds = seconds.abs();
+
+        return format!(
+            "{sign}{}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+    }
+
+    let (year, month, day, hour, minute, second, milli) = value.to_ymd_hms_milli();
+    let date = format!("{year:04}-{month:02}-{day:02}");
+    let mut time = format!("{hour:02}:{minute:02}:{second:02}");
+    if milli > 0 {
+        time.push_str(&format!(".{milli:03}"));
+    }
+
+    match value.as_f64() {
+        days if (0.0..1.0).contains(&days) => time,
+        _ if (hour, minute, second, milli) == (0, 0, 0, 0) => date,
+        _ => format!("{date}T{time}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calamine::{CellErrorType, ExcelDateTimeType};
+
+    use super::*;
+
+    #[test]
+    fn parses_sheets() {
+        assert_eq!("2".parse(), Ok(Sheet::Index(2)));
+        assert_eq!("0".parse(), Ok(Sheet::Name("0".to_string())));
+        assert_eq!("Sales".parse(), Ok(Sheet::Name("Sales".to_string())));
+    }
+
+    #[test]
+    fn names_columns() {
+        let mut range = Range::new((0, 0), (0, 2));
+        range.set_value((0, 0), Data::String("Order Date".to_string()));
+        range.set_value((0, 2), Data::String("Total-Sales ".to_string()));
+
+        let names = columns(&range)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Order_Date", "c2", "Total_Sales"]);
+    }
+
+    #[test]
+    fn converts_cells() {
+        let date =
+            |value, datetime_type| Data::DateTime(ExcelDateTime::new(value, datetime_type, false));
+        let mut range = Range::new((0, 0), (4, 2));
+        range.set_value((0, 0), Data::String("id".to_string()));
+        range.set_value((0, 2), Data::String("value".to_string()));
+        for (row, (id, value)) in [
+            (Data::Float(1.0), Data::Float(2.5)),
+            (Data::Int(2), Data::Bool(true)),
+            (Data::Empty, Data::Empty),
+            (Data::Float(4.0), date(45943.0, ExcelDateTimeType::DateTime)),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            range.set_value((row as u32 + 1, 0), id);
+            range.set_value((row as u32 + 1, 2), value);
+        }
+
+        let integer = |value| ColumnValue::Integer(IntegerColumnType(value));
+        let string = |value: &str| ColumnValue::String(StringColumnType(value.to_string()));
+        assert_eq!(
+            columns(&range),
+            vec![
+                ("id".to_string(), vec![integer(1), integer(2), integer(4)]),
+                ("c2".to_string(), vec![string(""), string(""), string("")]),
+                (
+                    "value".to_string(),
+                    vec![string("2.5"), string("true"), string("2025-10-13")]
+                ),
+            ]
+        );
+
+        assert_eq!(
+            to_column_value(&date(45943.541, ExcelDateTimeType::DateTime)),
+            string("2025-10-13T12:59:02.400")
+        );
+        assert_eq!(
+            to_column_value(&date(0.5, ExcelDateTimeType::DateTime)),
+            string("12:00:00")
+        );
+        assert_eq!(
+            to_column_value(&date(1.25, ExcelDateTimeType::TimeDelta)),
+            string("30:00:00")
+        );
+        assert_eq!(
+            to_column_value(&Data::Error(CellErrorType::Div0)),
+            string("#DIV/0!")
+        );
+    }
+}
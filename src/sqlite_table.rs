@@ -0,0 +1,311 @@
+//! The tables of the SQLite databases.
+
+use std::collections::HashMap;
+
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+
+use crate::{
+    column::Column,
+    error::Error as CrateError,
+    filter::{ApplyTableFilterByValue, FilterColumns, FilterQueryIterator},
+    table::{AsTable, ColumnType, ColumnValue, IntegerColumnType, LoadOnDemand, StringColumnType},
+};
+
+/// A table or a view of a SQLite database, read on demand.
+#[derive(Debug, Clone)]
+pub struct SqliteTable {
+    /// The path of the database.
+    path: String,
+    /// The name of the table in the database.
+    table: String,
+    /// The column names in the order of the table definition.
+    columns: Vec<String>,
+    /// The type of every column, if all its values are of the same type.
+    column_types: HashMap<String, Option<ColumnType>>,
+    /// The number of rows in the table.
+    row_count: usize,
+    /// The names and the values of the loaded columns, in the order of
+    /// the table definition.
+    data: Vec<(String, Column)>,
+}
+
+/// The type affinity of a SQLite column, following from its declared
+/// type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Affinity {
+    /// The declared type contains `INT`.
+    Integer,
+    /// The declared type contains `CHAR`, `CLOB` or `TEXT`.
+    Text,
+    /// The type is not declared or contains `BLOB`.
+    Blob,
+    /// The declared type contains `REAL`, `FLOA` or `DOUB`.
+    Real,
+    /// Any other declared type.
+    Numeric,
+}
+
+impl Affinity {
+    /// Returns the affinity of the declared type, by the rules of SQLite.
+    fn of(declared_type: &str) -> Self {
+        let declared_type = declared_type.to_ascii_uppercase();
+        let contains = |names: &[&str]| names.iter().any(|name| declared_type.contains(name));
+
+        if contains(&["INT"]) {
+            Affinity::Integer
+        } else if contains(&["CHAR", "CLOB", "TEXT"]) {
+            Affinity::Text
+        } else if declared_type.is_empty() || contains(&["BLOB"]) {
+            Affinity::Blob
+        } else if contains(&["REAL", "FLOA", "DOUB"]) {
+            Affinity::Real
+        } else {
+            Affinity::Numeric
+        }
+    }
+
+    /// Returns the type of the column of the affinity, given the number
+    /// of its integers out of all its values.
+    ///
+    /// SQLite doesn't enforce the declared types, so the integer and the
+    /// numeric columns are of the integers only if all the stored values
+    /// are, the nulls included, and the others are of the strings only if
+    /// none of them is an integer.
+    fn column_type(self, integers: usize, rows: usize) -> Option<ColumnType> {
+        match self {
+            Affinity::Integer | Affinity::Numeric => {
+                (integers == rows).then_some(ColumnType::Integer)
+            }
+            Affinity::Text | Affinity::Blob | Affinity::Real => {
+                (integers == 0).then_some(ColumnType::String)
+            }
+        }
+    }
+}
+
+impl SqliteTable {
+    /// Reads the schema of the table of the SQLite database, or of its
+    /// only table if no table is named.
+    pub fn from_sqlite(path: &str, table: Option<&str>) -> crate::error::Result<Self> {
+        let connection = open(path)?;
+
+        let tables = connection
+            .prepare(
+                "SELECT name FROM sqlite_schema \
+                 WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'",
+            )?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let table = match (table, tables.as_slice()) {
+            (Some(table), _) if tables.iter().any(|name| name == table) => table.to_owned(),
+            (Some(table), _) => return Err(CrateError::TableNotFound(table.to_owned())),
+            (None, [only]) => only.clone(),
+            (None, _) => {
+                return Err(CrateError::TableNotFound(format!(
+                    "none given, the database has {}",
+                    tables.join(", ")
+                )))
+            }
+        };
+
+        let schema = connection
+            .prepare("SELECT name, type FROM pragma_table_info(?1)")?
+            .query_map([&table], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let integers: String = schema
+            .iter()
+            .map(|(column, _)| format!(", SUM(typeof({}) = 'integer')", quote(column)))
+            .collect();
+        let counts = connection.query_row(
+            &format!("SELECT COUNT(*){integers} FROM {}", quote(&table)),
+            [],
+            |row| {
+                (0..=schema.len())
+                    .map(|i| Ok(row.get::<_, Option<i64>>(i)?.unwrap_or(0) as usize))
+                    .collect::<Result<Vec<_>, rusqlite::Error>>()
+            },
+        )?;
+        let row_count = counts[0];
+
+        let column_types = schema
+            .iter()
+            .zip(&counts[1..])
+            .map(|((column, declared_type), &integers)| {
+                let column_type = Affinity::of(declared_type).column_type(integers, row_count);
+                (column.clone(), column_type)
+            })
+            .collect();
+
+        Ok(Self {
+            path: path.to_owned(),
+            table,
+            columns: schema.into_iter().map(|(column, _)| column).collect(),
+            column_types,
+            row_count,
+            data: Vec::new(),
+        })
+    }
+
+    /// Returns the number of rows in the table.
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+}
+
+impl LoadOnDemand for SqliteTable {
+    fn load(&self, filter_columns: &FilterColumns) -> crate::error::Result<Self> {
+        let columns = filter_columns.referenced_columns(&self.columns)?;
+        let mut values: Vec<Vec<ColumnValue>> = vec![Vec::new(); columns.len()];
+
+        if !columns.is_empty() {
+            let select: Vec<String> = columns.iter().map(|column| quote(column)).collect();
+            let connection = open(&self.path)?;
+            let mut statement = connection.prepare(&format!(
+                "SELECT {} FROM {}",
+                select.join(", "),
+                quote(&self.table)
+            ))?;
+
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                for (i, values) in values.iter_mut().enumerate() {
+                    values.push(to_column_value(row.get_ref(i)?));
+                }
+            }
+        }
+
+        Ok(Self {
+            data: columns
+                .into_iter()
+                .cloned()
+                .zip(values.into_iter().map(Column::from_values))
+                .collect(),
+            ..self.clone()
+        })
+    }
+}
+
+/// Opens the SQLite database for reading.
+fn open(path: &str) -> rusqlite::Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+}
+
+/// Returns the identifier quoted for SQLite.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Converts the SQLite value into a column value. The nulls are empty,
+/// while the reals and the blobs become their text.
+fn to_column_value(value: ValueRef<'_>) -> ColumnValue {
+    match value {
+        ValueRef::Null => ColumnValue::null(),
+        ValueRef::Integer(value) => ColumnValue::Integer(IntegerColumnType(value)),
+        ValueRef::Real(value) => ColumnValue::String(StringColumnType(format!("{value:?}"))),
+        ValueRef::Text(value) | ValueRef::Blob(value) => ColumnValue::String(StringColumnType(
+            String::from_utf8_lossy(value).into_owned(),
+        )),
+    }
+}
+
+impl ApplyTableFilterByValue<'_> for SqliteTable {
+    fn apply_filter(&self, filter: &FilterColumns) -> FilterQueryIterator<'_> {
+        FilterQueryIterator::new(self.get_rows(), filter.clone())
+    }
+}
+
+impl AsTable for SqliteTable {
+    fn get_name(&self) -> &str {
+        "SQLite Table"
+    }
+
+    fn get_columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.data
+            .iter()
+            .map(|(name, column)| (name.as_str(), column))
+    }
+
+    fn get_column_names(&self) -> impl Iterator<Item = &String> {
+        self.columns.iter()
+    }
+
+    fn get_column_type(&self, name: &str) -> Option<ColumnType> {
+        self.column_types.get(name).copied().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn affinities() {
+        assert_eq!(Affinity::of("BIGINT"), Affinity::Integer);
+        assert_eq!(Affinity::of("varchar(10)"), Affinity::Text);
+        assert_eq!(Affinity::of(""), Affinity::Blob);
+        assert_eq!(Affinity::of("DOUBLE PRECISION"), Affinity::Real);
+        assert_eq!(Affinity::of("DECIMAL(10,5)"), Affinity::Numeric);
+        // The integer rule comes first.
+        assert_eq!(Affinity::of("FLOATING POINT"), Affinity::Integer);
+    }
+
+    #[test]
+    fn load_sqlite() {
+        let path = std::env::temp_dir().join(format!("prisma-test-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE countries (
+                     id INTEGER, name TEXT, area REAL, population INTEGER, code
+                 );
+                 INSERT INTO countries VALUES (1, 'France', 551695.5, 68, 'FR');
+                 INSERT INTO countries VALUES (2, 'Monaco', 2.02, NULL, 'MC');
+                 INSERT INTO countries VALUES (3, 'Chad', 1284000, 18, 33);
+                 CREATE TABLE [cities of 2025] (name TEXT);",
+            )
+            .unwrap();
+        drop(connection);
+
+        let path_str = path.to_str().unwrap();
+        let missing = SqliteTable::from_sqlite(path_str, Some("other"));
+        let unnamed = SqliteTable::from_sqlite(path_str, None);
+        let cities = SqliteTable::from_sqlite(path_str, Some("cities of 2025"));
+        let table = SqliteTable::from_sqlite(path_str, Some("countries"));
+        let result = table.and_then(|table| {
+            Ok((
+                table.clone(),
+                table.execute("PROJECT name, area, code FILTER id > 1")?,
+            ))
+        });
+        std::fs::remove_file(&path).unwrap();
+        let (table, result) = result.unwrap();
+
+        assert!(matches!(missing, Err(CrateError::TableNotFound(name)) if name == "other"));
+        assert!(matches!(unnamed, Err(CrateError::TableNotFound(_))));
+        assert_eq!(cities.unwrap().get_column_names().count(), 1);
+
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.get_column_type("id"), Some(ColumnType::Integer));
+        assert_eq!(table.get_column_type("name"), Some(ColumnType::String));
+        assert_eq!(table.get_column_type("area"), Some(ColumnType::String));
+        assert_eq!(table.get_column_type("population"), None);
+        assert_eq!(table.get_column_type("code"), None);
+
+        let string = |value: &str| ColumnValue::String(StringColumnType(value.to_string()));
+        assert_eq!(
+            result.into_rows(),
+            vec![
+                vec![string("Monaco"), string("2.02"), string("MC")],
+                vec![
+                    string("Chad"),
+                    string("1284000.0"),
+                    ColumnValue::Integer(IntegerColumnType(33)),
+                ],
+            ]
+        );
+    }
+}
@@ -5,8 +5,12 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
+use crate::column::Column;
 use crate::error::Result;
-use crate::filter::{ApplyColumnFilterByValue, FilterByValue};
+use crate::filter::{
+    ApplyColumnFilterByValue, ApplyTableFilterByValue, FilterByValue, FilterColumns,
+};
+use crate::result_set::ResultSet;
 
 /// The integers in the data table.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -141,6 +145,33 @@ pub enum ColumnType {
     String,
 }
 
+impl ColumnType {
+    /// Returns the name of the column type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "Integer",
+            ColumnType::String => "String",
+        }
+    }
+}
+
+impl FromStr for ColumnType {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        [ColumnType::Integer, ColumnType::String]
+            .into_iter()
+            .find(|column_type| column_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| crate::error::Error::ValueParse(format!("Unknown column type: {s}")))
+    }
+}
+
+impl std::fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// A single column value.
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub enum ColumnValue {
@@ -167,6 +198,16 @@ impl ColumnValue {
         }
     }
 
+    /// Returns the null value, which is an empty cell.
+    pub fn null() -> Self {
+        ColumnValue::String(StringColumnType(String::new()))
+    }
+
+    /// Returns [`true`] if the value is null, which is an empty cell.
+    pub fn is_null(&self) -> bool {
+        matches!(self, ColumnValue::String(value) if value.is_empty())
+    }
+
     /// Returns the column type of the value.
     pub fn get_type(&self) -> ColumnType {
         match self {
@@ -213,17 +254,63 @@ pub trait AsTable {
 
     /// Returns the columns of the table. The first element of the tuple
     /// is the column name, and the second element is the column values.
-    fn get_columns(&self) -> impl Iterator<Item = (&str, &[ColumnValue])>;
+    fn get_columns(&self) -> impl Iterator<Item = (&str, &Column)>;
 
     /// Returns the names of the columns in the table.
     fn get_column_names(&self) -> impl Iterator<Item = &String>;
 
+    /// Returns the type of the column, if all its values are of the same
+    /// type. Returns [`None`] for an unknown, empty or mixed-type column.
+    fn get_column_type(&self, name: &str) -> Option<ColumnType>;
+
     /// Returns an iterator over the values in the table.
     fn get_values(&self) -> impl Iterator<Item = (&str, &ColumnValue)> {
         self.get_columns()
             .flat_map(|(name, values)| values.iter().map(move |value| (name, value)))
     }
 
+    /// Returns the row with the given number, its values by column name.
+    fn get_row(&self, row_idx: usize) -> HashMap<String, &ColumnValue> {
+        self.get_columns()
+            .filter_map(|(name, values)| Some((name.to_owned(), values.get(row_idx)?)))
+            .collect()
+    }
+
     /// Returns an iterator over the rows in the table.
-    fn get_rows(&self) -> Box<dyn Iterator<Item = HashMap<String, &ColumnValue>> + '_>;
+    fn get_rows(&self) -> Box<dyn Iterator<Item = HashMap<String, &ColumnValue>> + '_> {
+        // Assume all columns have the same number of rows.
+        let row_count = self
+            .get_columns()
+            .next()
+            .map_or(0, |(_, values)| values.len());
+
+        Box::new((0..row_count).map(move |row_idx| self.get_row(row_idx)))
+    }
+}
+
+/// A table read on demand: only its schema is read up front, and every
+/// query then loads the values of the columns it refers to.
+pub trait LoadOnDemand: AsTable + for<'a> ApplyTableFilterByValue<'a> + Sized {
+    /// Returns the table with the values of the columns the query refers
+    /// to loaded.
+    fn load(&self, filter_columns: &FilterColumns) -> Result<Self>;
+
+    /// Queries the table with a filter and returns the result.
+    fn execute<F>(&self, filter_columns: F) -> Result<ResultSet>
+    where
+        FilterColumns: TryFrom<F>,
+        crate::error::Error: From<<FilterColumns as TryFrom<F>>::Error>,
+    {
+        let filter_columns = FilterColumns::try_from(filter_columns)?;
+
+        ResultSet::execute(&filter_columns, &self.load(&filter_columns)?)
+    }
+}
+
+/// Returns the name with the characters not allowed in the table and the
+/// column names of the statements replaced by `_`.
+pub fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
 }
@@ -0,0 +1,70 @@
+//! The per-block column statistics (zone maps) used to skip the rows
+//! which cannot satisfy a filter.
+
+use crate::{
+    column::Column,
+    filter::{FilterByValue, Operation},
+    table::ColumnValue,
+};
+
+/// The number of rows in a block described by a single zone map.
+pub const BLOCK_SIZE: usize = 1024;
+
+/// The statistics of a block of rows of a single column.
+#[derive(Debug, Clone)]
+pub struct ZoneMap {
+    /// The smallest value in the block.
+    pub min: ColumnValue,
+    /// The largest value in the block.
+    pub max: ColumnValue,
+    /// The number of null (empty) values in the block.
+    pub null_count: usize,
+}
+
+impl ZoneMap {
+    /// Builds the zone maps of the column, one per [`BLOCK_SIZE`] rows.
+    pub fn build(column: &Column) -> Vec<Self> {
+        (0..column.len())
+            .step_by(BLOCK_SIZE)
+            .filter_map(|start| {
+                let rows = start..(start + BLOCK_SIZE).min(column.len());
+                let mut values = rows.filter_map(|row_idx| column.get(row_idx));
+
+                let first = values.next()?;
+                let mut zone_map = Self {
+                    min: first.clone(),
+                    max: first.clone(),
+                    null_count: usize::from(first.is_null()),
+                };
+
+                for value in values {
+                    if value < &zone_map.min {
+                        zone_map.min = value.clone();
+                    }
+                    if value > &zone_map.max {
+                        zone_map.max = value.clone();
+                    }
+                    zone_map.null_count += usize::from(value.is_null());
+                }
+
+                Some(zone_map)
+            })
+            .collect()
+    }
+
+    /// Returns [`false`] if none of the values in the block can satisfy
+    /// the filter, so the block can be skipped.
+    ///
+    /// The values of different types are ordered by their type first,
+    /// so the range check stays correct for the mixed-type blocks.
+    pub fn may_match(&self, filter: &FilterByValue) -> bool {
+        let value = &filter.value;
+
+        match filter.operation {
+            Operation::Equal if value.is_null() => self.null_count > 0,
+            Operation::Equal => &self.min <= value && value <= &self.max,
+            Operation::GreaterThan => &self.max > value,
+            Operation::LessThan => &self.min < value,
+        }
+    }
+}